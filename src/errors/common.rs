@@ -1,9 +1,12 @@
 use derive_more::Display;
 
+use crate::services::email::EmailError;
+
 #[derive(Debug, Display)]
 pub enum CommonError {
     Validation(validator::ValidationErrors),
     Database(surrealdb::Error),
-    Email(resend_rs::Error),
+    Email(EmailError),
     Hashing(argon2::password_hash::Error),
+    Encryption(aes_gcm::Error),
 }
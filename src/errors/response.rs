@@ -6,6 +6,8 @@ use axum::{
 use hyper::StatusCode;
 use serde_json::{json, Value};
 
+use crate::services::i18n::translate;
+
 pub trait ErrorResponse {
     fn error_name(&self) -> &str;
     fn error_message(&self) -> Value;
@@ -24,6 +26,21 @@ impl<T: ErrorResponse> IntoResponse for ApiError<T> {
     }
 }
 
+// Maps every invalid field to the list of messages of the rules it broke
+pub fn validation_error_map(errors: &validator::ValidationErrors) -> Value {
+    let mut field_errors = serde_json::Map::new();
+    for (field, error_vec) in errors.field_errors() {
+        let messages: Vec<String> = error_vec
+            .iter()
+            .filter_map(|error| error.message.as_ref().map(|msg| msg.to_string()))
+            .collect();
+        if !messages.is_empty() {
+            field_errors.insert(field.to_string(), json!(messages));
+        }
+    }
+    json!(field_errors)
+}
+
 impl ErrorResponse for CommonError {
     fn error_name(&self) -> &str {
         match self {
@@ -31,27 +48,17 @@ impl ErrorResponse for CommonError {
             CommonError::Database(_) => "Database Error",
             CommonError::Email(_) => "Email Service Error",
             CommonError::Hashing(_) => "Hashing Error",
+            CommonError::Encryption(_) => "Encryption Error",
         }
     }
 
     fn error_message(&self) -> Value {
         match self {
-            CommonError::Validation(errors) => {
-                let mut field_errors = serde_json::Map::new();
-                for (field, error_vec) in errors.field_errors() {
-                    let messages: Vec<String> = error_vec
-                        .iter()
-                        .filter_map(|error| error.message.as_ref().map(|msg| msg.to_string()))
-                        .collect();
-                    if !messages.is_empty() {
-                        field_errors.insert(field.to_string(), json!(messages));
-                    }
-                }
-                json!(field_errors)
-            }
+            CommonError::Validation(errors) => validation_error_map(errors),
             CommonError::Database(e) => json!(e.to_string()),
-            CommonError::Email(_) => json!("An error occurred while sending email"),
-            CommonError::Hashing(_) => json!("An error occurred while processing credentials"),
+            CommonError::Email(_) => json!(translate("error-email-service")),
+            CommonError::Hashing(_) => json!(translate("error-hashing")),
+            CommonError::Encryption(_) => json!(translate("error-encryption")),
         }
     }
 
@@ -61,6 +68,7 @@ impl ErrorResponse for CommonError {
             CommonError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             CommonError::Email(_) => StatusCode::INTERNAL_SERVER_ERROR,
             CommonError::Hashing(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            CommonError::Encryption(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
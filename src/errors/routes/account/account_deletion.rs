@@ -0,0 +1,81 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
+
+#[derive(Debug, Display)]
+pub enum AccountDeletionError {
+    Common(CommonError),
+    InvalidPassword,
+    InvalidToken,
+}
+
+impl ErrorResponse for AccountDeletionError {
+    fn error_name(&self) -> &str {
+        match self {
+            AccountDeletionError::Common(e) => e.error_name(),
+            AccountDeletionError::InvalidPassword => "Invalid Password",
+            AccountDeletionError::InvalidToken => "Invalid Token",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            AccountDeletionError::Common(e) => e.error_message(),
+            AccountDeletionError::InvalidPassword => json!(translate("error-password-incorrect")),
+            AccountDeletionError::InvalidToken => {
+                json!(translate("error-account-deletion-token-invalid"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AccountDeletionError::Common(e) => e.status_code(),
+            AccountDeletionError::InvalidPassword => StatusCode::UNAUTHORIZED,
+            AccountDeletionError::InvalidToken => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<CommonError> for AccountDeletionError {
+    fn from(error: CommonError) -> Self {
+        AccountDeletionError::Common(error)
+    }
+}
+
+impl From<AccountDeletionError> for ApiError<AccountDeletionError> {
+    fn from(error: AccountDeletionError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<AccountDeletionError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(AccountDeletionError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<AccountDeletionError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(AccountDeletionError::Common(CommonError::Database(error)))
+    }
+}
+
+impl From<EmailError> for ApiError<AccountDeletionError> {
+    fn from(error: EmailError) -> Self {
+        ApiError(AccountDeletionError::Common(CommonError::Email(error)))
+    }
+}
+
+impl From<argon2::password_hash::Error> for ApiError<AccountDeletionError> {
+    fn from(error: argon2::password_hash::Error) -> Self {
+        ApiError(AccountDeletionError::Common(CommonError::Hashing(error)))
+    }
+}
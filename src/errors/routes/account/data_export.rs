@@ -0,0 +1,75 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
+
+#[derive(Debug, Display)]
+pub enum DataExportError {
+    Common(CommonError),
+    InvalidToken,
+    TokenExpired,
+    ExportCooldown,
+}
+
+impl ErrorResponse for DataExportError {
+    fn error_name(&self) -> &str {
+        match self {
+            DataExportError::Common(e) => e.error_name(),
+            DataExportError::InvalidToken => "Invalid Token",
+            DataExportError::TokenExpired => "Token Expired",
+            DataExportError::ExportCooldown => "Export Cooldown",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            DataExportError::Common(e) => e.error_message(),
+            DataExportError::InvalidToken => json!(translate("error-data-export-invalid")),
+            DataExportError::TokenExpired => {
+                json!(translate("error-data-export-expired"))
+            }
+            DataExportError::ExportCooldown => {
+                json!(translate("error-data-export-cooldown"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            DataExportError::Common(e) => e.status_code(),
+            DataExportError::InvalidToken => StatusCode::NOT_FOUND,
+            DataExportError::TokenExpired => StatusCode::GONE,
+            DataExportError::ExportCooldown => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+impl From<CommonError> for DataExportError {
+    fn from(error: CommonError) -> Self {
+        DataExportError::Common(error)
+    }
+}
+
+impl From<DataExportError> for ApiError<DataExportError> {
+    fn from(error: DataExportError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<surrealdb::Error> for ApiError<DataExportError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(DataExportError::Common(CommonError::Database(error)))
+    }
+}
+
+impl From<EmailError> for ApiError<DataExportError> {
+    fn from(error: EmailError) -> Self {
+        ApiError(DataExportError::Common(CommonError::Email(error)))
+    }
+}
@@ -0,0 +1,111 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
+
+#[derive(Debug, Display)]
+pub enum EmailChangeError {
+    Common(CommonError),
+    InvalidPassword,
+    SameEmail,
+    EmailAlreadyExists,
+    NoPendingChange,
+    InvalidToken,
+    InvalidCode,
+    ExpiredCode,
+    TooManyAttempts,
+}
+
+impl ErrorResponse for EmailChangeError {
+    fn error_name(&self) -> &str {
+        match self {
+            EmailChangeError::Common(e) => e.error_name(),
+            EmailChangeError::InvalidPassword => "Invalid Password",
+            EmailChangeError::SameEmail => "Same Email",
+            EmailChangeError::EmailAlreadyExists => "Email Already Exists",
+            EmailChangeError::NoPendingChange => "No Pending Change",
+            EmailChangeError::InvalidToken => "Invalid Token",
+            EmailChangeError::InvalidCode => "Invalid Code",
+            EmailChangeError::ExpiredCode => "Expired Code",
+            EmailChangeError::TooManyAttempts => "Too Many Attempts",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            EmailChangeError::Common(e) => e.error_message(),
+            EmailChangeError::InvalidPassword => json!(translate("error-password-incorrect")),
+            EmailChangeError::SameEmail => {
+                json!(translate("error-email-unchanged"))
+            }
+            EmailChangeError::EmailAlreadyExists => {
+                json!(translate("error-email-already-exists"))
+            }
+            EmailChangeError::NoPendingChange => {
+                json!(translate("error-email-change-not-pending"))
+            }
+            EmailChangeError::InvalidToken => json!(translate("error-email-change-token-invalid")),
+            EmailChangeError::InvalidCode => json!(translate("error-verification-code-invalid")),
+            EmailChangeError::ExpiredCode => json!(translate("error-verification-code-expired")),
+            EmailChangeError::TooManyAttempts => {
+                json!(translate("error-email-change-too-many-attempts"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            EmailChangeError::Common(e) => e.status_code(),
+            EmailChangeError::InvalidPassword => StatusCode::UNAUTHORIZED,
+            EmailChangeError::SameEmail => StatusCode::BAD_REQUEST,
+            EmailChangeError::EmailAlreadyExists => StatusCode::CONFLICT,
+            EmailChangeError::NoPendingChange => StatusCode::NOT_FOUND,
+            EmailChangeError::InvalidToken => StatusCode::BAD_REQUEST,
+            EmailChangeError::InvalidCode => StatusCode::BAD_REQUEST,
+            EmailChangeError::ExpiredCode => StatusCode::BAD_REQUEST,
+            EmailChangeError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+impl From<CommonError> for EmailChangeError {
+    fn from(error: CommonError) -> Self {
+        EmailChangeError::Common(error)
+    }
+}
+
+impl From<EmailChangeError> for ApiError<EmailChangeError> {
+    fn from(error: EmailChangeError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<EmailChangeError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(EmailChangeError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<EmailChangeError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(EmailChangeError::Common(CommonError::Database(error)))
+    }
+}
+
+impl From<EmailError> for ApiError<EmailChangeError> {
+    fn from(error: EmailError) -> Self {
+        ApiError(EmailChangeError::Common(CommonError::Email(error)))
+    }
+}
+
+impl From<argon2::password_hash::Error> for ApiError<EmailChangeError> {
+    fn from(error: argon2::password_hash::Error) -> Self {
+        ApiError(EmailChangeError::Common(CommonError::Hashing(error)))
+    }
+}
@@ -0,0 +1,70 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::i18n::{translate_with_args, FluentArgs, Locale},
+};
+
+#[derive(Debug, Display)]
+pub enum LocaleError {
+    Common(CommonError),
+    UnsupportedLocale,
+}
+
+impl ErrorResponse for LocaleError {
+    fn error_name(&self) -> &str {
+        match self {
+            LocaleError::Common(e) => e.error_name(),
+            LocaleError::UnsupportedLocale => "Unsupported Locale",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            LocaleError::Common(e) => e.error_message(),
+            LocaleError::UnsupportedLocale => {
+                let locales: Vec<&str> = Locale::ALL.iter().map(Locale::code).collect();
+
+                json!(translate_with_args(
+                    "error-locale-unsupported",
+                    &FluentArgs::from_iter([("locales", locales.join(", "))]),
+                ))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            LocaleError::Common(e) => e.status_code(),
+            LocaleError::UnsupportedLocale => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<CommonError> for LocaleError {
+    fn from(error: CommonError) -> Self {
+        LocaleError::Common(error)
+    }
+}
+
+impl From<LocaleError> for ApiError<LocaleError> {
+    fn from(error: LocaleError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<LocaleError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(LocaleError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<LocaleError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(LocaleError::Common(CommonError::Database(error)))
+    }
+}
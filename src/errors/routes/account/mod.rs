@@ -0,0 +1,11 @@
+mod account_deletion;
+mod data_export;
+mod email_change;
+mod locale;
+mod password_change;
+
+pub use account_deletion::AccountDeletionError;
+pub use data_export::DataExportError;
+pub use email_change::EmailChangeError;
+pub use locale::LocaleError;
+pub use password_change::PasswordChangeError;
@@ -0,0 +1,94 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{
+        response::{validation_error_map, ApiError},
+        CommonError, ErrorResponse,
+    },
+    services::{email::EmailError, i18n::translate},
+};
+
+#[derive(Debug, Display)]
+pub enum PasswordChangeError {
+    Common(CommonError),
+    InvalidCurrentPassword,
+    AccountLocked,
+    WeakPassword(validator::ValidationErrors),
+    BreachedPassword,
+}
+
+impl ErrorResponse for PasswordChangeError {
+    fn error_name(&self) -> &str {
+        match self {
+            PasswordChangeError::Common(e) => e.error_name(),
+            PasswordChangeError::InvalidCurrentPassword => "Invalid Current Password",
+            PasswordChangeError::AccountLocked => "Account Locked",
+            PasswordChangeError::WeakPassword(_) => "Weak Password",
+            PasswordChangeError::BreachedPassword => "Breached Password",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            PasswordChangeError::Common(e) => e.error_message(),
+            PasswordChangeError::InvalidCurrentPassword => {
+                json!(translate("error-current-password-incorrect"))
+            }
+            PasswordChangeError::AccountLocked => json!(translate("error-account-locked")),
+            PasswordChangeError::WeakPassword(errors) => validation_error_map(errors),
+            PasswordChangeError::BreachedPassword => {
+                json!(translate("error-password-breached"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PasswordChangeError::Common(e) => e.status_code(),
+            PasswordChangeError::InvalidCurrentPassword => StatusCode::UNAUTHORIZED,
+            PasswordChangeError::AccountLocked => StatusCode::FORBIDDEN,
+            PasswordChangeError::WeakPassword(_) => StatusCode::BAD_REQUEST,
+            PasswordChangeError::BreachedPassword => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<CommonError> for PasswordChangeError {
+    fn from(error: CommonError) -> Self {
+        PasswordChangeError::Common(error)
+    }
+}
+
+impl From<PasswordChangeError> for ApiError<PasswordChangeError> {
+    fn from(error: PasswordChangeError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<PasswordChangeError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(PasswordChangeError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<PasswordChangeError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(PasswordChangeError::Common(CommonError::Database(error)))
+    }
+}
+
+impl From<EmailError> for ApiError<PasswordChangeError> {
+    fn from(error: EmailError) -> Self {
+        ApiError(PasswordChangeError::Common(CommonError::Email(error)))
+    }
+}
+
+impl From<argon2::password_hash::Error> for ApiError<PasswordChangeError> {
+    fn from(error: argon2::password_hash::Error) -> Self {
+        ApiError(PasswordChangeError::Common(CommonError::Hashing(error)))
+    }
+}
@@ -0,0 +1,69 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::i18n::translate,
+};
+
+#[derive(Debug, Display)]
+pub enum AccountUnlockError {
+    Common(CommonError),
+    InvalidToken,
+    TokenExpired,
+}
+
+impl ErrorResponse for AccountUnlockError {
+    fn error_name(&self) -> &str {
+        match self {
+            AccountUnlockError::Common(e) => e.error_name(),
+            AccountUnlockError::InvalidToken => "Invalid Token",
+            AccountUnlockError::TokenExpired => "Token Expired",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            AccountUnlockError::Common(e) => e.error_message(),
+            AccountUnlockError::InvalidToken => {
+                json!(translate("error-unlock-token-invalid"))
+            }
+            AccountUnlockError::TokenExpired => json!(translate("error-unlock-token-expired")),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AccountUnlockError::Common(e) => e.status_code(),
+            AccountUnlockError::InvalidToken => StatusCode::UNAUTHORIZED,
+            AccountUnlockError::TokenExpired => StatusCode::UNAUTHORIZED,
+        }
+    }
+}
+
+impl From<CommonError> for AccountUnlockError {
+    fn from(error: CommonError) -> Self {
+        AccountUnlockError::Common(error)
+    }
+}
+
+impl From<AccountUnlockError> for ApiError<AccountUnlockError> {
+    fn from(error: AccountUnlockError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<AccountUnlockError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(AccountUnlockError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<AccountUnlockError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(AccountUnlockError::Common(CommonError::Database(error)))
+    }
+}
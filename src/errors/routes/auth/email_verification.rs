@@ -2,7 +2,10 @@ use derive_more::Display;
 use hyper::StatusCode;
 use serde_json::{json, Value};
 
-use crate::errors::{response::ApiError, CommonError, ErrorResponse};
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
 
 #[derive(Debug, Display)]
 pub enum EmailVerificationError {
@@ -13,6 +16,9 @@ pub enum EmailVerificationError {
     InvalidCode,
     ExpiredCode,
     CodeAlreadyUsed,
+    TooManyAttempts,
+    ResendCooldown,
+    ResendLimitReached,
 }
 
 impl ErrorResponse for EmailVerificationError {
@@ -25,19 +31,41 @@ impl ErrorResponse for EmailVerificationError {
             EmailVerificationError::InvalidCode => "Invalid Code",
             EmailVerificationError::ExpiredCode => "Expired Code",
             EmailVerificationError::CodeAlreadyUsed => "Code Already Used",
+            EmailVerificationError::TooManyAttempts => "Too Many Attempts",
+            EmailVerificationError::ResendCooldown => "Resend Cooldown",
+            EmailVerificationError::ResendLimitReached => "Resend Limit Reached",
         }
     }
 
     fn error_message(&self) -> Value {
         match self {
             EmailVerificationError::Common(e) => e.error_message(),
-            EmailVerificationError::TokenExpired => json!("The verification token has expired"),
-            EmailVerificationError::InvalidToken => json!("The verification token is invalid"),
-            EmailVerificationError::EmailAlreadyVerified => json!("The email is already verified"),
-            EmailVerificationError::InvalidCode => json!("The verification code is invalid"),
-            EmailVerificationError::ExpiredCode => json!("The verification code has expired"),
+            EmailVerificationError::TokenExpired => {
+                json!(translate("error-verification-token-expired"))
+            }
+            EmailVerificationError::InvalidToken => {
+                json!(translate("error-verification-token-invalid"))
+            }
+            EmailVerificationError::EmailAlreadyVerified => {
+                json!(translate("error-email-already-verified"))
+            }
+            EmailVerificationError::InvalidCode => {
+                json!(translate("error-verification-code-invalid"))
+            }
+            EmailVerificationError::ExpiredCode => {
+                json!(translate("error-verification-code-expired"))
+            }
             EmailVerificationError::CodeAlreadyUsed => {
-                json!("The verification code has already been used")
+                json!(translate("error-verification-code-used"))
+            }
+            EmailVerificationError::TooManyAttempts => {
+                json!(translate("error-verification-too-many-attempts"))
+            }
+            EmailVerificationError::ResendCooldown => {
+                json!(translate("error-verification-resend-cooldown"))
+            }
+            EmailVerificationError::ResendLimitReached => {
+                json!(translate("error-verification-resend-limit"))
             }
         }
     }
@@ -51,6 +79,9 @@ impl ErrorResponse for EmailVerificationError {
             EmailVerificationError::InvalidCode => StatusCode::BAD_REQUEST,
             EmailVerificationError::ExpiredCode => StatusCode::BAD_REQUEST,
             EmailVerificationError::CodeAlreadyUsed => StatusCode::BAD_REQUEST,
+            EmailVerificationError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+            EmailVerificationError::ResendCooldown => StatusCode::TOO_MANY_REQUESTS,
+            EmailVerificationError::ResendLimitReached => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
@@ -83,8 +114,8 @@ impl From<surrealdb::Error> for ApiError<EmailVerificationError> {
     }
 }
 
-impl From<resend_rs::Error> for ApiError<EmailVerificationError> {
-    fn from(error: resend_rs::Error) -> Self {
+impl From<EmailError> for ApiError<EmailVerificationError> {
+    fn from(error: EmailError) -> Self {
         ApiError(EmailVerificationError::Common(CommonError::Email(error)))
     }
 }
@@ -0,0 +1,103 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
+
+#[derive(Debug, Display)]
+pub enum MagicLinkError {
+    Common(CommonError),
+    InvalidToken,
+    TokenExpired,
+    InvalidCode,
+    TooManyAttempts,
+    MissingCredentials,
+    AccountLocked,
+    AccountNotVerified,
+    AccountPendingDeletion,
+}
+
+impl ErrorResponse for MagicLinkError {
+    fn error_name(&self) -> &str {
+        match self {
+            MagicLinkError::Common(e) => e.error_name(),
+            MagicLinkError::InvalidToken => "Invalid Token",
+            MagicLinkError::TokenExpired => "Token Expired",
+            MagicLinkError::InvalidCode => "Invalid Code",
+            MagicLinkError::TooManyAttempts => "Too Many Attempts",
+            MagicLinkError::MissingCredentials => "Missing Credentials",
+            MagicLinkError::AccountLocked => "Account Locked",
+            MagicLinkError::AccountNotVerified => "Account Not Verified",
+            MagicLinkError::AccountPendingDeletion => "Account Pending Deletion",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            MagicLinkError::Common(e) => e.error_message(),
+            MagicLinkError::InvalidToken => json!(translate("error-magic-link-invalid")),
+            MagicLinkError::TokenExpired => json!(translate("error-magic-link-expired")),
+            MagicLinkError::InvalidCode => json!(translate("error-magic-link-code-invalid")),
+            MagicLinkError::TooManyAttempts => {
+                json!(translate("error-magic-link-too-many-attempts"))
+            }
+            MagicLinkError::MissingCredentials => {
+                json!(translate("error-magic-link-missing-credentials"))
+            }
+            MagicLinkError::AccountLocked => json!(translate("error-account-locked")),
+            MagicLinkError::AccountNotVerified => json!(translate("error-account-not-verified")),
+            MagicLinkError::AccountPendingDeletion => {
+                json!(translate("error-account-pending-deletion"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            MagicLinkError::Common(e) => e.status_code(),
+            MagicLinkError::InvalidToken => StatusCode::UNAUTHORIZED,
+            MagicLinkError::TokenExpired => StatusCode::UNAUTHORIZED,
+            MagicLinkError::InvalidCode => StatusCode::UNAUTHORIZED,
+            MagicLinkError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+            MagicLinkError::MissingCredentials => StatusCode::BAD_REQUEST,
+            MagicLinkError::AccountLocked => StatusCode::FORBIDDEN,
+            MagicLinkError::AccountNotVerified => StatusCode::FORBIDDEN,
+            MagicLinkError::AccountPendingDeletion => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl From<CommonError> for MagicLinkError {
+    fn from(error: CommonError) -> Self {
+        MagicLinkError::Common(error)
+    }
+}
+
+impl From<MagicLinkError> for ApiError<MagicLinkError> {
+    fn from(error: MagicLinkError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<MagicLinkError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(MagicLinkError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<MagicLinkError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(MagicLinkError::Common(CommonError::Database(error)))
+    }
+}
+
+impl From<EmailError> for ApiError<MagicLinkError> {
+    fn from(error: EmailError) -> Self {
+        ApiError(MagicLinkError::Common(CommonError::Email(error)))
+    }
+}
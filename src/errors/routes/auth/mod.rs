@@ -1,11 +1,25 @@
+mod account_unlock;
 mod email_verification;
+mod magic_link;
+mod passkey;
 mod password_reset;
 mod password_reset_request;
+mod session;
+mod sessions;
 mod signin;
+mod signout;
 mod signup;
+mod two_factor;
 
+pub use account_unlock::AccountUnlockError;
 pub use email_verification::EmailVerificationError;
+pub use magic_link::MagicLinkError;
+pub use passkey::PasskeyError;
 pub use password_reset::PasswordResetError;
 pub use password_reset_request::PasswordResetRequestError;
+pub use session::SessionError;
+pub use sessions::SessionsError;
 pub use signin::SigninError;
+pub use signout::SignoutError;
 pub use signup::SignupError;
+pub use two_factor::TwoFactorError;
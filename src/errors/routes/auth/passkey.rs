@@ -0,0 +1,105 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::i18n::translate,
+};
+
+#[derive(Debug, Display)]
+pub enum PasskeyError {
+    Common(CommonError),
+    InvalidChallenge,
+    ChallengeExpired,
+    VerificationFailed,
+    UnsupportedCredential,
+    CredentialNotFound,
+    CredentialAlreadyRegistered,
+    AccountNotVerified,
+    AccountPendingDeletion,
+}
+
+impl ErrorResponse for PasskeyError {
+    fn error_name(&self) -> &str {
+        match self {
+            PasskeyError::Common(e) => e.error_name(),
+            PasskeyError::InvalidChallenge => "Invalid Challenge",
+            PasskeyError::ChallengeExpired => "Challenge Expired",
+            PasskeyError::VerificationFailed => "Verification Failed",
+            PasskeyError::UnsupportedCredential => "Unsupported Credential",
+            PasskeyError::CredentialNotFound => "Credential Not Found",
+            PasskeyError::CredentialAlreadyRegistered => "Credential Already Registered",
+            PasskeyError::AccountNotVerified => "Account Not Verified",
+            PasskeyError::AccountPendingDeletion => "Account Pending Deletion",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            PasskeyError::Common(e) => e.error_message(),
+            PasskeyError::InvalidChallenge => json!(translate("error-passkey-challenge-invalid")),
+            PasskeyError::ChallengeExpired => json!(translate("error-passkey-challenge-expired")),
+            PasskeyError::VerificationFailed => {
+                json!(translate("error-passkey-verification-failed"))
+            }
+            PasskeyError::UnsupportedCredential => {
+                json!(translate("error-passkey-unsupported"))
+            }
+            PasskeyError::CredentialNotFound => json!(translate("error-passkey-not-found")),
+            PasskeyError::CredentialAlreadyRegistered => {
+                json!(translate("error-passkey-already-registered"))
+            }
+            PasskeyError::AccountNotVerified => json!(translate("error-account-not-verified")),
+            PasskeyError::AccountPendingDeletion => {
+                json!(translate("error-account-pending-deletion"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PasskeyError::Common(e) => e.status_code(),
+            PasskeyError::InvalidChallenge => StatusCode::BAD_REQUEST,
+            PasskeyError::ChallengeExpired => StatusCode::BAD_REQUEST,
+            PasskeyError::VerificationFailed => StatusCode::UNAUTHORIZED,
+            PasskeyError::UnsupportedCredential => StatusCode::BAD_REQUEST,
+            PasskeyError::CredentialNotFound => StatusCode::UNAUTHORIZED,
+            PasskeyError::CredentialAlreadyRegistered => StatusCode::CONFLICT,
+            PasskeyError::AccountNotVerified => StatusCode::FORBIDDEN,
+            PasskeyError::AccountPendingDeletion => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl From<CommonError> for PasskeyError {
+    fn from(error: CommonError) -> Self {
+        PasskeyError::Common(error)
+    }
+}
+
+impl From<PasskeyError> for ApiError<PasskeyError> {
+    fn from(error: PasskeyError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<PasskeyError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(PasskeyError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<argon2::password_hash::Error> for ApiError<PasskeyError> {
+    fn from(error: argon2::password_hash::Error) -> Self {
+        ApiError(PasskeyError::Common(CommonError::Hashing(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<PasskeyError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(PasskeyError::Common(CommonError::Database(error)))
+    }
+}
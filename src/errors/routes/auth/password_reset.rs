@@ -2,7 +2,13 @@ use derive_more::Display;
 use hyper::StatusCode;
 use serde_json::{json, Value};
 
-use crate::errors::{response::ApiError, CommonError, ErrorResponse};
+use crate::{
+    errors::{
+        response::{validation_error_map, ApiError},
+        CommonError, ErrorResponse,
+    },
+    services::{email::EmailError, i18n::translate},
+};
 
 #[derive(Debug, Display)]
 pub enum PasswordResetError {
@@ -10,6 +16,8 @@ pub enum PasswordResetError {
     TokenExpired,
     InvalidToken,
     InvalidEmail,
+    WeakPassword(validator::ValidationErrors),
+    BreachedPassword,
 }
 
 impl ErrorResponse for PasswordResetError {
@@ -19,6 +27,8 @@ impl ErrorResponse for PasswordResetError {
             PasswordResetError::TokenExpired => "Token Expired",
             PasswordResetError::InvalidToken => "Invalid Token",
             PasswordResetError::InvalidEmail => "Invalid Email",
+            PasswordResetError::WeakPassword(_) => "Weak Password",
+            PasswordResetError::BreachedPassword => "Breached Password",
         }
     }
 
@@ -26,10 +36,16 @@ impl ErrorResponse for PasswordResetError {
         match self {
             PasswordResetError::Common(e) => e.error_message(),
             PasswordResetError::TokenExpired => {
-                json!("The password reset token has expired")
+                json!(translate("error-password-reset-token-expired"))
+            }
+            PasswordResetError::InvalidToken => {
+                json!(translate("error-password-reset-token-invalid"))
+            }
+            PasswordResetError::InvalidEmail => json!(translate("error-email-invalid")),
+            PasswordResetError::WeakPassword(errors) => validation_error_map(errors),
+            PasswordResetError::BreachedPassword => {
+                json!(translate("error-password-breached"))
             }
-            PasswordResetError::InvalidToken => json!("The password reset token is invalid"),
-            PasswordResetError::InvalidEmail => json!("The provided email is invalid"),
         }
     }
 
@@ -39,6 +55,8 @@ impl ErrorResponse for PasswordResetError {
             PasswordResetError::TokenExpired => StatusCode::BAD_REQUEST,
             PasswordResetError::InvalidToken => StatusCode::BAD_REQUEST,
             PasswordResetError::InvalidEmail => StatusCode::BAD_REQUEST,
+            PasswordResetError::WeakPassword(_) => StatusCode::BAD_REQUEST,
+            PasswordResetError::BreachedPassword => StatusCode::BAD_REQUEST,
         }
     }
 }
@@ -69,8 +87,8 @@ impl From<surrealdb::Error> for ApiError<PasswordResetError> {
     }
 }
 
-impl From<resend_rs::Error> for ApiError<PasswordResetError> {
-    fn from(error: resend_rs::Error) -> Self {
+impl From<EmailError> for ApiError<PasswordResetError> {
+    fn from(error: EmailError) -> Self {
         ApiError(PasswordResetError::Common(CommonError::Email(error)))
     }
 }
@@ -2,7 +2,10 @@ use derive_more::Display;
 use hyper::StatusCode;
 use serde_json::{json, Value};
 
-use crate::errors::{response::ApiError, CommonError, ErrorResponse};
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
 
 #[derive(Debug, Display)]
 pub enum PasswordResetRequestError {
@@ -26,10 +29,12 @@ impl ErrorResponse for PasswordResetRequestError {
         match self {
             PasswordResetRequestError::Common(e) => e.error_message(),
             PasswordResetRequestError::TokenExpired => {
-                json!("The password reset token has expired")
+                json!(translate("error-password-reset-token-expired"))
             }
-            PasswordResetRequestError::InvalidToken => json!("The password reset token is invalid"),
-            PasswordResetRequestError::InvalidEmail => json!("The provided email is invalid"),
+            PasswordResetRequestError::InvalidToken => {
+                json!(translate("error-password-reset-token-invalid"))
+            }
+            PasswordResetRequestError::InvalidEmail => json!(translate("error-email-invalid")),
         }
     }
 
@@ -73,8 +78,8 @@ impl From<surrealdb::Error> for ApiError<PasswordResetRequestError> {
     }
 }
 
-impl From<resend_rs::Error> for ApiError<PasswordResetRequestError> {
-    fn from(error: resend_rs::Error) -> Self {
+impl From<EmailError> for ApiError<PasswordResetRequestError> {
+    fn from(error: EmailError) -> Self {
         ApiError(PasswordResetRequestError::Common(CommonError::Email(error)))
     }
 }
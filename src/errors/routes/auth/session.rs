@@ -0,0 +1,71 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::i18n::translate,
+};
+
+#[derive(Debug, Display)]
+pub enum SessionError {
+    Common(CommonError),
+    MissingSession,
+    InvalidSession,
+    SessionExpired,
+    SessionUnauthorized,
+}
+
+impl ErrorResponse for SessionError {
+    fn error_name(&self) -> &str {
+        match self {
+            SessionError::Common(e) => e.error_name(),
+            SessionError::MissingSession => "Missing Session",
+            SessionError::InvalidSession => "Invalid Session",
+            SessionError::SessionExpired => "Session Expired",
+            SessionError::SessionUnauthorized => "Session Unauthorized",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            SessionError::Common(e) => e.error_message(),
+            SessionError::MissingSession => json!(translate("error-session-missing")),
+            SessionError::InvalidSession => json!(translate("error-session-invalid")),
+            SessionError::SessionExpired => json!(translate("error-session-expired")),
+            SessionError::SessionUnauthorized => {
+                json!(translate("error-session-unauthorized"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SessionError::Common(e) => e.status_code(),
+            SessionError::MissingSession => StatusCode::UNAUTHORIZED,
+            SessionError::InvalidSession => StatusCode::UNAUTHORIZED,
+            SessionError::SessionExpired => StatusCode::UNAUTHORIZED,
+            SessionError::SessionUnauthorized => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl From<CommonError> for SessionError {
+    fn from(error: CommonError) -> Self {
+        SessionError::Common(error)
+    }
+}
+
+impl From<SessionError> for ApiError<SessionError> {
+    fn from(error: SessionError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<surrealdb::Error> for ApiError<SessionError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(SessionError::Common(CommonError::Database(error)))
+    }
+}
@@ -0,0 +1,59 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::i18n::translate,
+};
+
+#[derive(Debug, Display)]
+pub enum SessionsError {
+    Common(CommonError),
+    SessionNotFound,
+}
+
+impl ErrorResponse for SessionsError {
+    fn error_name(&self) -> &str {
+        match self {
+            SessionsError::Common(e) => e.error_name(),
+            SessionsError::SessionNotFound => "Session Not Found",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            SessionsError::Common(e) => e.error_message(),
+            SessionsError::SessionNotFound => {
+                json!(translate("error-session-not-found"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SessionsError::Common(e) => e.status_code(),
+            SessionsError::SessionNotFound => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
+impl From<CommonError> for SessionsError {
+    fn from(error: CommonError) -> Self {
+        SessionsError::Common(error)
+    }
+}
+
+impl From<SessionsError> for ApiError<SessionsError> {
+    fn from(error: SessionsError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<surrealdb::Error> for ApiError<SessionsError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(SessionsError::Common(CommonError::Database(error)))
+    }
+}
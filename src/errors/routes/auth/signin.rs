@@ -2,7 +2,10 @@ use derive_more::Display;
 use hyper::StatusCode;
 use serde_json::{json, Value};
 
-use crate::errors::{response::ApiError, CommonError, ErrorResponse};
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::{email::EmailError, i18n::translate},
+};
 
 #[derive(Debug, Display)]
 pub enum SigninError {
@@ -10,6 +13,11 @@ pub enum SigninError {
     InvalidCredentials,
     AccountLocked,
     AccountNotVerified,
+    AccountPendingDeletion,
+    TooManyAttempts,
+    TwoFactorNotRequired,
+    InvalidTwoFactorCode,
+    InvalidRecoveryCode,
 }
 
 impl ErrorResponse for SigninError {
@@ -19,15 +27,37 @@ impl ErrorResponse for SigninError {
             SigninError::InvalidCredentials => "Invalid Credentials",
             SigninError::AccountLocked => "Account Locked",
             SigninError::AccountNotVerified => "Account Not Verified",
+            SigninError::AccountPendingDeletion => "Account Pending Deletion",
+            SigninError::TooManyAttempts => "Too Many Attempts",
+            SigninError::TwoFactorNotRequired => "Two Factor Not Required",
+            SigninError::InvalidTwoFactorCode => "Invalid Two Factor Code",
+            SigninError::InvalidRecoveryCode => "Invalid Recovery Code",
         }
     }
 
     fn error_message(&self) -> Value {
         match self {
             SigninError::Common(e) => e.error_message(),
-            SigninError::InvalidCredentials => json!("The provided credentials are invalid"),
-            SigninError::AccountLocked => json!("The account is locked"),
-            SigninError::AccountNotVerified => json!("The account is not verified"),
+            SigninError::InvalidCredentials => json!(translate("error-invalid-credentials")),
+            SigninError::AccountLocked => {
+                json!(translate("error-account-locked"))
+            }
+            SigninError::AccountNotVerified => json!(translate("error-account-not-verified")),
+            SigninError::AccountPendingDeletion => {
+                json!(translate("error-account-pending-deletion"))
+            }
+            SigninError::TooManyAttempts => {
+                json!(translate("error-signin-too-many-attempts"))
+            }
+            SigninError::TwoFactorNotRequired => {
+                json!(translate("error-two-factor-not-required"))
+            }
+            SigninError::InvalidTwoFactorCode => {
+                json!(translate("error-two-factor-code-invalid"))
+            }
+            SigninError::InvalidRecoveryCode => {
+                json!(translate("error-recovery-code-invalid"))
+            }
         }
     }
 
@@ -37,6 +67,11 @@ impl ErrorResponse for SigninError {
             SigninError::InvalidCredentials => StatusCode::UNAUTHORIZED,
             SigninError::AccountLocked => StatusCode::FORBIDDEN,
             SigninError::AccountNotVerified => StatusCode::FORBIDDEN,
+            SigninError::AccountPendingDeletion => StatusCode::FORBIDDEN,
+            SigninError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+            SigninError::TwoFactorNotRequired => StatusCode::BAD_REQUEST,
+            SigninError::InvalidTwoFactorCode => StatusCode::UNAUTHORIZED,
+            SigninError::InvalidRecoveryCode => StatusCode::UNAUTHORIZED,
         }
     }
 }
@@ -67,8 +102,8 @@ impl From<surrealdb::Error> for ApiError<SigninError> {
     }
 }
 
-impl From<resend_rs::Error> for ApiError<SigninError> {
-    fn from(error: resend_rs::Error) -> Self {
+impl From<EmailError> for ApiError<SigninError> {
+    fn from(error: EmailError) -> Self {
         ApiError(SigninError::Common(CommonError::Email(error)))
     }
 }
@@ -78,3 +113,9 @@ impl From<argon2::password_hash::Error> for ApiError<SigninError> {
         ApiError(SigninError::Common(CommonError::Hashing(error)))
     }
 }
+
+impl From<aes_gcm::Error> for ApiError<SigninError> {
+    fn from(error: aes_gcm::Error) -> Self {
+        ApiError(SigninError::Common(CommonError::Encryption(error)))
+    }
+}
@@ -0,0 +1,50 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::Value;
+
+use crate::errors::{response::ApiError, CommonError, ErrorResponse};
+
+#[derive(Debug, Display)]
+pub enum SignoutError {
+    Common(CommonError),
+}
+
+impl ErrorResponse for SignoutError {
+    fn error_name(&self) -> &str {
+        match self {
+            SignoutError::Common(e) => e.error_name(),
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            SignoutError::Common(e) => e.error_message(),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SignoutError::Common(e) => e.status_code(),
+        }
+    }
+}
+
+impl From<CommonError> for SignoutError {
+    fn from(error: CommonError) -> Self {
+        SignoutError::Common(error)
+    }
+}
+
+impl From<SignoutError> for ApiError<SignoutError> {
+    fn from(error: SignoutError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<surrealdb::Error> for ApiError<SignoutError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(SignoutError::Common(CommonError::Database(error)))
+    }
+}
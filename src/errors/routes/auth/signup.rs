@@ -2,13 +2,20 @@ use derive_more::Display;
 use hyper::StatusCode;
 use serde_json::{json, Value};
 
-use crate::errors::{response::ApiError, CommonError, ErrorResponse};
+use crate::{
+    errors::{
+        response::{validation_error_map, ApiError},
+        CommonError, ErrorResponse,
+    },
+    services::{email::EmailError, i18n::translate},
+};
 
 #[derive(Debug, Display)]
 pub enum SignupError {
     Common(CommonError),
     EmailAlreadyExists,
-    WeakPassword,
+    WeakPassword(validator::ValidationErrors),
+    BreachedPassword,
     InvalidReferralCode,
     RegistrationClosed,
 }
@@ -18,7 +25,8 @@ impl ErrorResponse for SignupError {
         match self {
             SignupError::Common(e) => e.error_name(),
             SignupError::EmailAlreadyExists => "Email Already Exists",
-            SignupError::WeakPassword => "Weak Password",
+            SignupError::WeakPassword(_) => "Weak Password",
+            SignupError::BreachedPassword => "Breached Password",
             SignupError::InvalidReferralCode => "Invalid Referral Code",
             SignupError::RegistrationClosed => "Registration Closed",
         }
@@ -28,16 +36,17 @@ impl ErrorResponse for SignupError {
         match self {
             SignupError::Common(e) => e.error_message(),
             SignupError::EmailAlreadyExists => {
-                json!("An account with this email already exists")
+                json!(translate("error-email-already-exists"))
             }
-            SignupError::WeakPassword => {
-                json!("Password does not meet the minimum strength requirements")
+            SignupError::WeakPassword(errors) => validation_error_map(errors),
+            SignupError::BreachedPassword => {
+                json!(translate("error-password-breached"))
             }
             SignupError::InvalidReferralCode => {
-                json!("The provided referral code is invalid")
+                json!(translate("error-referral-code-invalid"))
             }
             SignupError::RegistrationClosed => {
-                json!("Registration is currently closed for new users")
+                json!(translate("error-registration-closed"))
             }
         }
     }
@@ -46,7 +55,8 @@ impl ErrorResponse for SignupError {
         match self {
             SignupError::Common(e) => e.status_code(),
             SignupError::EmailAlreadyExists => StatusCode::CONFLICT,
-            SignupError::WeakPassword => StatusCode::BAD_REQUEST,
+            SignupError::WeakPassword(_) => StatusCode::BAD_REQUEST,
+            SignupError::BreachedPassword => StatusCode::BAD_REQUEST,
             SignupError::InvalidReferralCode => StatusCode::BAD_REQUEST,
             SignupError::RegistrationClosed => StatusCode::FORBIDDEN,
         }
@@ -79,8 +89,8 @@ impl From<surrealdb::Error> for ApiError<SignupError> {
     }
 }
 
-impl From<resend_rs::Error> for ApiError<SignupError> {
-    fn from(error: resend_rs::Error) -> Self {
+impl From<EmailError> for ApiError<SignupError> {
+    fn from(error: EmailError) -> Self {
         ApiError(SignupError::Common(CommonError::Email(error)))
     }
 }
@@ -0,0 +1,101 @@
+use derive_more::Display;
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{response::ApiError, CommonError, ErrorResponse},
+    services::i18n::translate,
+};
+
+#[derive(Debug, Display)]
+pub enum TwoFactorError {
+    Common(CommonError),
+    AlreadyEnabled,
+    NotSetUp,
+    NotEnabled,
+    InvalidCode,
+    TooManyAttempts,
+}
+
+impl ErrorResponse for TwoFactorError {
+    fn error_name(&self) -> &str {
+        match self {
+            TwoFactorError::Common(e) => e.error_name(),
+            TwoFactorError::AlreadyEnabled => "Two Factor Already Enabled",
+            TwoFactorError::NotSetUp => "Two Factor Not Set Up",
+            TwoFactorError::NotEnabled => "Two Factor Not Enabled",
+            TwoFactorError::InvalidCode => "Invalid Code",
+            TwoFactorError::TooManyAttempts => "Too Many Attempts",
+        }
+    }
+
+    fn error_message(&self) -> Value {
+        match self {
+            TwoFactorError::Common(e) => e.error_message(),
+            TwoFactorError::AlreadyEnabled => {
+                json!(translate("error-two-factor-already-enabled"))
+            }
+            TwoFactorError::NotSetUp => {
+                json!(translate("error-two-factor-setup-not-started"))
+            }
+            TwoFactorError::NotEnabled => {
+                json!(translate("error-two-factor-not-enabled"))
+            }
+            TwoFactorError::InvalidCode => {
+                json!(translate("error-two-factor-code-invalid"))
+            }
+            TwoFactorError::TooManyAttempts => {
+                json!(translate("error-two-factor-too-many-attempts"))
+            }
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            TwoFactorError::Common(e) => e.status_code(),
+            TwoFactorError::AlreadyEnabled => StatusCode::CONFLICT,
+            TwoFactorError::NotSetUp => StatusCode::BAD_REQUEST,
+            TwoFactorError::NotEnabled => StatusCode::BAD_REQUEST,
+            TwoFactorError::InvalidCode => StatusCode::BAD_REQUEST,
+            TwoFactorError::TooManyAttempts => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+impl From<CommonError> for TwoFactorError {
+    fn from(error: CommonError) -> Self {
+        TwoFactorError::Common(error)
+    }
+}
+
+impl From<TwoFactorError> for ApiError<TwoFactorError> {
+    fn from(error: TwoFactorError) -> Self {
+        ApiError(error)
+    }
+}
+
+// Automatic Error Conversion
+
+impl From<validator::ValidationErrors> for ApiError<TwoFactorError> {
+    fn from(error: validator::ValidationErrors) -> Self {
+        ApiError(TwoFactorError::Common(CommonError::Validation(error)))
+    }
+}
+
+impl From<surrealdb::Error> for ApiError<TwoFactorError> {
+    fn from(error: surrealdb::Error) -> Self {
+        ApiError(TwoFactorError::Common(CommonError::Database(error)))
+    }
+}
+
+impl From<aes_gcm::Error> for ApiError<TwoFactorError> {
+    fn from(error: aes_gcm::Error) -> Self {
+        ApiError(TwoFactorError::Common(CommonError::Encryption(error)))
+    }
+}
+
+impl From<argon2::password_hash::Error> for ApiError<TwoFactorError> {
+    fn from(error: argon2::password_hash::Error) -> Self {
+        ApiError(TwoFactorError::Common(CommonError::Hashing(error)))
+    }
+}
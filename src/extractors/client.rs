@@ -0,0 +1,94 @@
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+};
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRef, FromRequestParts},
+    http::request::Parts,
+};
+use hyper::{header::USER_AGENT, HeaderMap};
+
+use crate::{
+    services::database::session::SessionMetadata, setup::AppState, utils::device::describe_device,
+};
+
+// Details about the client making the request, used to label sessions
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn session_metadata(&self) -> SessionMetadata {
+        SessionMetadata {
+            user_agent: self.user_agent.clone(),
+            ip_address: self.ip_address.clone(),
+            device_label: describe_device(self.user_agent.as_deref()),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let app_state = AppState::from_ref(state);
+
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        let peer_ip = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip());
+
+        let ip_address = match peer_ip {
+            Some(peer_ip) if app_state.config.trusted_proxies.contains(&peer_ip) => {
+                forwarded_ip(&parts.headers, &app_state.config.trusted_proxies).or(Some(peer_ip))
+            }
+            peer_ip => peer_ip,
+        };
+
+        Ok(Self {
+            user_agent,
+            ip_address: ip_address.map(|ip_address| ip_address.to_string()),
+        })
+    }
+}
+
+// Only called for requests coming from a trusted proxy, anyone else could set these headers.
+// Every proxy appends the address it received the request from to `x-forwarded-for`, so the
+// client is the last address that isn't one of the trusted proxies
+fn forwarded_ip(headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let forwarded_for: Vec<IpAddr> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|value| value.trim().parse().ok())
+        .collect();
+
+    if !forwarded_for.is_empty() {
+        return forwarded_for
+            .iter()
+            .rev()
+            .find(|address| !trusted_proxies.contains(address))
+            .or(forwarded_for.first())
+            .copied();
+    }
+
+    headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod session;
+
+pub use client::ClientInfo;
+pub use session::{AnySession, AuthSession};
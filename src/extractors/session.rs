@@ -0,0 +1,126 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts, Extension};
+use axum_extra::extract::CookieJar;
+use chrono::Utc;
+use surrealdb::sql::Thing;
+
+use crate::{
+    errors::{auth::SessionError, response::ApiError, CommonError},
+    services::{
+        database::{session::Session, user::User, DatabaseLayer},
+        i18n::{set_current_locale, Locale},
+    },
+    utils::{cookies::SESSION_COOKIE_NAME, crypto::hash_token},
+};
+
+// Session of a signed in user, rejects requests without an authorized session
+pub struct AuthSession {
+    pub session: Session,
+    pub user: User,
+}
+
+// Any valid session, including the unauthorized ones created during signup
+pub struct AnySession {
+    pub session: Session,
+    pub user: User,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthSession
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError<SessionError>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (session, user) = load_session(parts, state).await?;
+
+        if !session.authorized {
+            return Err(ApiError(SessionError::SessionUnauthorized));
+        }
+
+        Ok(Self { session, user })
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AnySession
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError<SessionError>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (session, user) = load_session(parts, state).await?;
+
+        Ok(Self { session, user })
+    }
+}
+
+async fn load_session<S>(
+    parts: &mut Parts,
+    state: &S,
+) -> Result<(Session, User), ApiError<SessionError>>
+where
+    S: Send + Sync,
+{
+    // 1. Read the session token from the cookie
+
+    let jar = CookieJar::from_request_parts(parts, state)
+        .await
+        .map_err(|_| ApiError(SessionError::MissingSession))?;
+
+    let session_token = match jar.get(SESSION_COOKIE_NAME) {
+        Some(cookie) => cookie.value().to_string(),
+        None => return Err(ApiError(SessionError::MissingSession)),
+    };
+
+    let Extension(database_layer) = Extension::<DatabaseLayer>::from_request_parts(parts, state)
+        .await
+        .map_err(|_| {
+            ApiError(SessionError::Common(CommonError::Database(
+                surrealdb::Error::Api(surrealdb::error::Api::InvalidRequest(String::from(
+                    "Database layer is not available",
+                ))),
+            )))
+        })?;
+
+    // 2. Retrieve the session, only the token hash is stored in the database
+
+    let session_id = Thing::from((String::from("session"), hash_token(&session_token)));
+
+    let session = match database_layer.query().session.get(session_id.clone()).await {
+        Ok(session) => session,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(SessionError::InvalidSession));
+        }
+        Err(err) => return Err(ApiError(SessionError::Common(CommonError::Database(err)))),
+    };
+
+    if *session.expires_at < Utc::now() {
+        return Err(ApiError(SessionError::SessionExpired));
+    }
+
+    // 3. Retrieve the session owner and mark the session as used, the rest of the request is
+    // handled in the locale the user picked
+
+    let user = match database_layer
+        .query()
+        .user
+        .get_by_id(session.user.clone())
+        .await
+    {
+        Ok(user) => user,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(SessionError::InvalidSession));
+        }
+        Err(err) => return Err(ApiError(SessionError::Common(CommonError::Database(err)))),
+    };
+
+    if let Some(locale) = user.locale.as_deref().and_then(Locale::from_code) {
+        set_current_locale(locale);
+    }
+
+    database_layer.query().session.touch(session_id).await?;
+
+    Ok((session, user))
+}
@@ -0,0 +1,49 @@
+# Copy of the transactional emails, the layout and markup live in the email templates
+
+email-footer-sent-by = Diese E-Mail wurde von { $brand } gesendet.
+email-footer-support = Fragen? Schreib uns an { $address }.
+email-code-expiry = Der Code läuft in { $minutes } Minuten ab.
+email-verification-reference = Bestätigungsreferenz: { $reference }
+email-verification-subject = Kontoaktivierung
+email-verification-intro = Willkommen bei { $brand }! Nutze den folgenden Code, um dein Konto zu aktivieren.
+email-verification-confirmation-subject = E-Mail-Adresse erfolgreich bestätigt
+email-verification-confirmation-body = Deine E-Mail-Adresse wurde bestätigt, dein Konto ist jetzt vollständig aktiviert.
+email-password-reset-subject = Passwort zurücksetzen
+email-password-reset-intro = Für dein Konto wurde das Zurücksetzen des Passworts angefordert. Über den folgenden Link kannst du ein neues Passwort wählen.
+email-password-reset-button = Passwort zurücksetzen
+email-password-reset-ignore = Wenn du das nicht angefordert hast, kannst du diese E-Mail ignorieren.
+email-password-reset-confirmation-subject = Dein Passwort wurde geändert
+email-password-reset-confirmation-body = Dein Passwort wurde geändert und alle deine Sitzungen wurden abgemeldet. Wenn du das nicht warst, setze dein Passwort sofort zurück.
+email-password-changed-subject = Dein Passwort wurde geändert
+email-password-changed-body = Dein Passwort wurde geändert und alle anderen Sitzungen wurden abgemeldet. Wenn du das nicht warst, setze dein Passwort sofort zurück.
+email-change-verification-subject = Bestätige deine neue E-Mail-Adresse
+email-change-verification-intro = Nutze den folgenden Code, um diese Adresse als neue E-Mail-Adresse deines Kontos zu bestätigen.
+email-change-requested-subject = Änderung der E-Mail-Adresse angefordert
+email-change-requested-body = Es wurde angefordert, die E-Mail-Adresse deines Kontos in { $new_email } zu ändern.
+email-change-requested-warning = Wenn du das nicht warst, brich die Änderung ab und setze dein Passwort zurück.
+email-change-requested-button = Änderung abbrechen
+email-changed-subject = Deine E-Mail-Adresse wurde geändert
+email-changed-body = Die E-Mail-Adresse deines Kontos wurde in { $new_email } geändert. Wenn du das nicht warst, kontaktiere sofort den Support.
+email-account-deletion-scheduled-subject = Dein Konto ist zur Löschung vorgemerkt
+email-account-deletion-scheduled-body = Dein Konto und alle zugehörigen Daten werden am { $scheduled_at } endgültig gelöscht.
+email-account-deletion-scheduled-note = Falls du es dir anders überlegst, kannst du die Löschung bis dahin abbrechen.
+email-account-deletion-scheduled-button = Löschung abbrechen
+email-account-deleted-subject = Dein Konto wurde gelöscht
+email-account-deleted-body = Dein Konto und alle zugehörigen Daten wurden endgültig gelöscht.
+email-data-export-subject = Dein Datenexport ist bereit
+email-data-export-body = Der Export deiner Kontodaten ist bereit.
+email-data-export-button = Herunterladen
+email-data-export-expiry = Der Link läuft am { $expires_at } ab.
+email-magic-link-subject = Dein Anmeldelink
+email-magic-link-intro = Melde dich über den folgenden Link an oder gib den Code auf der Anmeldeseite ein.
+email-magic-link-button = Anmelden
+email-magic-link-ignore = Wenn du dich nicht anmelden wolltest, kannst du diese E-Mail ignorieren.
+email-recovery-code-used-subject = Wiederherstellungscode verwendet
+email-recovery-code-used-body = Gerade wurde ein Wiederherstellungscode verwendet, um dich bei deinem Konto anzumelden. { $remaining ->
+        [one] Du hast noch 1 Wiederherstellungscode.
+       *[other] Du hast noch { $remaining } Wiederherstellungscodes.
+    } Wenn du das nicht warst, setze dein Passwort sofort zurück.
+email-account-locked-subject = Dein Konto wurde gesperrt
+email-account-locked-body = Dein Konto wurde nach zu vielen fehlgeschlagenen Anmeldeversuchen für { $minutes } Minuten gesperrt.
+email-account-locked-hint = Wenn du das warst, kannst du es sofort entsperren. Andernfalls solltest du dein Passwort zurücksetzen.
+email-account-locked-button = Konto entsperren
@@ -0,0 +1,69 @@
+# Messages returned in the `message` field of error responses
+
+error-email-service = Beim Senden der E-Mail ist ein Fehler aufgetreten
+error-hashing = Bei der Verarbeitung der Anmeldedaten ist ein Fehler aufgetreten
+error-encryption = Bei der Verarbeitung geheimer Daten ist ein Fehler aufgetreten
+error-unlock-token-invalid = Der Entsperrlink ist ungültig oder wurde bereits verwendet
+error-unlock-token-expired = Der Entsperrlink ist abgelaufen
+error-password-reset-token-expired = Der Link zum Zurücksetzen des Passworts ist abgelaufen
+error-password-reset-token-invalid = Der Link zum Zurücksetzen des Passworts ist ungültig
+error-email-invalid = Die angegebene E-Mail-Adresse ist ungültig
+error-session-not-found = Die Sitzung existiert nicht oder gehört nicht zu diesem Konto
+error-password-breached = Das Passwort ist in einem bekannten Datenleck aufgetaucht, bitte wähle ein anderes
+error-verification-token-expired = Der Bestätigungslink ist abgelaufen
+error-verification-token-invalid = Der Bestätigungslink ist ungültig
+error-email-already-verified = Die E-Mail-Adresse ist bereits bestätigt
+error-verification-code-invalid = Der Bestätigungscode ist ungültig
+error-verification-code-expired = Der Bestätigungscode ist abgelaufen
+error-verification-code-used = Der Bestätigungscode wurde bereits verwendet
+error-verification-too-many-attempts = Zu viele ungültige Versuche, fordere einen neuen Bestätigungscode an
+error-verification-resend-cooldown = Es wurde gerade ein Bestätigungscode gesendet, warte eine Minute, bevor du einen neuen anforderst
+error-verification-resend-limit = Das tägliche Limit für Bestätigungs-E-Mails wurde erreicht
+error-magic-link-invalid = Der Anmeldelink ist ungültig oder wurde bereits verwendet
+error-magic-link-expired = Der Anmeldelink ist abgelaufen
+error-magic-link-code-invalid = Der Anmeldecode ist ungültig
+error-magic-link-too-many-attempts = Zu viele ungültige Versuche, fordere einen neuen Anmeldelink an
+error-magic-link-missing-credentials = Es muss entweder ein Token oder eine E-Mail-Adresse mit Code angegeben werden
+error-account-not-verified = Das Konto ist nicht bestätigt
+error-account-pending-deletion = Das Konto ist zur Löschung vorgemerkt, nutze den Link aus der Lösch-E-Mail, um die Löschung abzubrechen
+error-two-factor-already-enabled = Die Zwei-Faktor-Authentifizierung ist bereits aktiviert
+error-two-factor-setup-not-started = Die Einrichtung der Zwei-Faktor-Authentifizierung wurde nicht gestartet
+error-two-factor-not-enabled = Die Zwei-Faktor-Authentifizierung ist nicht aktiviert
+error-two-factor-code-invalid = Der Code der Zwei-Faktor-Authentifizierung ist ungültig
+error-two-factor-too-many-attempts = Zu viele ungültige Codes, starte die Einrichtung der Zwei-Faktor-Authentifizierung erneut
+error-passkey-challenge-invalid = Die Passkey-Anfrage ist ungültig
+error-passkey-challenge-expired = Die Passkey-Anfrage ist abgelaufen
+error-passkey-verification-failed = Der Passkey konnte nicht überprüft werden
+error-passkey-unsupported = Es werden nur ES256-Passkeys unterstützt
+error-passkey-not-found = Der Passkey ist nicht registriert
+error-passkey-already-registered = Der Passkey ist bereits registriert
+error-email-already-exists = Es gibt bereits ein Konto mit dieser E-Mail-Adresse
+error-referral-code-invalid = Der Empfehlungscode ist ungültig
+error-registration-closed = Die Registrierung für neue Nutzer ist derzeit geschlossen
+error-invalid-credentials = Die Anmeldedaten sind ungültig
+error-account-locked = Das Konto ist wegen zu vieler fehlgeschlagener Anmeldeversuche vorübergehend gesperrt
+error-signin-too-many-attempts = Zu viele fehlgeschlagene Anmeldeversuche, bitte versuche es später erneut
+error-two-factor-not-required = Die Sitzung benötigt keinen zweiten Faktor
+error-recovery-code-invalid = Der Wiederherstellungscode ist ungültig oder wurde bereits verwendet
+error-session-missing = Es wurde keine Sitzung übermittelt
+error-session-invalid = Die Sitzung ist ungültig
+error-session-expired = Die Sitzung ist abgelaufen
+error-session-unauthorized = Die Sitzung ist nicht berechtigt, auf diese Ressource zuzugreifen
+error-data-export-invalid = Der Download-Link ist ungültig
+error-data-export-expired = Der Download-Link ist abgelaufen, fordere einen neuen Export an
+error-data-export-cooldown = Es wurde gerade ein Datenexport angefordert, warte eine Stunde, bevor du einen neuen anforderst
+error-password-incorrect = Das Passwort ist falsch
+error-email-unchanged = Die neue E-Mail-Adresse entspricht der aktuellen
+error-email-change-not-pending = Es gibt keine ausstehende E-Mail-Änderung, sie wurde entweder abgeschlossen oder abgebrochen
+error-email-change-token-invalid = Der Link zur E-Mail-Änderung ist ungültig
+error-email-change-too-many-attempts = Zu viele ungültige Versuche, fordere die E-Mail-Änderung erneut an
+error-current-password-incorrect = Das aktuelle Passwort ist falsch
+error-account-deletion-token-invalid = Der Abbruchlink ist ungültig oder die Löschung wurde bereits abgebrochen
+error-locale-unsupported = Die Sprache wird nicht unterstützt, verwende eine von { $locales }
+validation-code-length = Der Code muss genau 6 Ziffern lang sein
+validation-code-format = Der Code darf nur Ziffern enthalten
+validation-password-too-short = Das Passwort muss mindestens { $min } Zeichen lang sein
+validation-password-too-long = Das Passwort darf höchstens { $max } Zeichen lang sein
+validation-password-common = Das Passwort ist zu verbreitet
+validation-password-contains-email = Das Passwort darf die E-Mail-Adresse nicht enthalten
+validation-password-too-weak = Das Passwort ist zu leicht zu erraten, versuche ein längeres Passwort oder eine Passphrase
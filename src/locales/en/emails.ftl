@@ -0,0 +1,49 @@
+# Copy of the transactional emails, the layout and markup live in the email templates
+
+email-footer-sent-by = This email was sent by { $brand }.
+email-footer-support = Questions? Contact us at { $address }.
+email-code-expiry = The code expires in { $minutes } minutes.
+email-verification-reference = Verification reference: { $reference }
+email-verification-subject = Account Activation
+email-verification-intro = Welcome to { $brand }! Use the code below to activate your account.
+email-verification-confirmation-subject = Email verified successfully
+email-verification-confirmation-body = Your email was verified, your account is now fully activated.
+email-password-reset-subject = Password Reset
+email-password-reset-intro = A password reset was requested for your account. Use the link below to choose a new password.
+email-password-reset-button = Reset password
+email-password-reset-ignore = If you didn't request a password reset, you can ignore this email.
+email-password-reset-confirmation-subject = Your password was changed
+email-password-reset-confirmation-body = Your password was changed and all of your sessions were signed out. If this wasn't you, reset your password immediately.
+email-password-changed-subject = Your password was changed
+email-password-changed-body = Your password was changed and all of your other sessions were signed out. If this wasn't you, reset your password immediately.
+email-change-verification-subject = Confirm your new email
+email-change-verification-intro = Use the code below to confirm this address as the new email of your account.
+email-change-requested-subject = Email change requested
+email-change-requested-body = A request was made to change the email of your account to { $new_email }.
+email-change-requested-warning = If this wasn't you, cancel the change and reset your password.
+email-change-requested-button = Cancel email change
+email-changed-subject = Your email was changed
+email-changed-body = The email of your account was changed to { $new_email }. If this wasn't you, contact support immediately.
+email-account-deletion-scheduled-subject = Your account is scheduled for deletion
+email-account-deletion-scheduled-body = Your account and all of its data will be permanently deleted on { $scheduled_at }.
+email-account-deletion-scheduled-note = If you changed your mind, you can cancel the deletion until then.
+email-account-deletion-scheduled-button = Cancel deletion
+email-account-deleted-subject = Your account was deleted
+email-account-deleted-body = Your account and all of its data were permanently deleted.
+email-data-export-subject = Your data export is ready
+email-data-export-body = The export of your account data is ready.
+email-data-export-button = Download
+email-data-export-expiry = The link expires on { $expires_at }.
+email-magic-link-subject = Your signin link
+email-magic-link-intro = Use the link below to sign in, or enter the code on the signin page.
+email-magic-link-button = Sign in
+email-magic-link-ignore = If you didn't try to sign in, you can ignore this email.
+email-recovery-code-used-subject = Recovery code used
+email-recovery-code-used-body = A recovery code was just used to sign in to your account. { $remaining ->
+        [one] You have 1 recovery code left.
+       *[other] You have { $remaining } recovery codes left.
+    } If this wasn't you, reset your password immediately.
+email-account-locked-subject = Your account was locked
+email-account-locked-body = Your account was locked for { $minutes } minutes after too many failed signin attempts.
+email-account-locked-hint = If this was you, you can unlock it right away. Otherwise, consider resetting your password.
+email-account-locked-button = Unlock account
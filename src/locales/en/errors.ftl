@@ -0,0 +1,69 @@
+# Messages returned in the `message` field of error responses
+
+error-email-service = An error occurred while sending email
+error-hashing = An error occurred while processing credentials
+error-encryption = An error occurred while processing secrets
+error-unlock-token-invalid = The provided unlock token is invalid or has already been used
+error-unlock-token-expired = The provided unlock token has expired
+error-password-reset-token-expired = The password reset token has expired
+error-password-reset-token-invalid = The password reset token is invalid
+error-email-invalid = The provided email is invalid
+error-session-not-found = The session doesn't exist or doesn't belong to the user
+error-password-breached = The password appeared in a known data breach, please choose a different one
+error-verification-token-expired = The verification token has expired
+error-verification-token-invalid = The verification token is invalid
+error-email-already-verified = The email is already verified
+error-verification-code-invalid = The verification code is invalid
+error-verification-code-expired = The verification code has expired
+error-verification-code-used = The verification code has already been used
+error-verification-too-many-attempts = Too many invalid attempts, request a new verification code
+error-verification-resend-cooldown = A verification code was sent recently, wait a minute before requesting another one
+error-verification-resend-limit = The daily limit of verification emails has been reached
+error-magic-link-invalid = The signin link is invalid or was already used
+error-magic-link-expired = The signin link has expired
+error-magic-link-code-invalid = The signin code is invalid
+error-magic-link-too-many-attempts = Too many invalid attempts, request a new signin link
+error-magic-link-missing-credentials = Either a token or an email and a code have to be provided
+error-account-not-verified = The account is not verified
+error-account-pending-deletion = The account is scheduled for deletion, use the link from the deletion email to cancel it
+error-two-factor-already-enabled = Two-factor authentication is already enabled
+error-two-factor-setup-not-started = Two-factor authentication setup has not been started
+error-two-factor-not-enabled = Two-factor authentication is not enabled
+error-two-factor-code-invalid = The provided two-factor authentication code is invalid
+error-two-factor-too-many-attempts = Too many invalid codes, start the two-factor authentication setup again
+error-passkey-challenge-invalid = The passkey challenge is invalid
+error-passkey-challenge-expired = The passkey challenge has expired
+error-passkey-verification-failed = The passkey could not be verified
+error-passkey-unsupported = Only ES256 passkeys are supported
+error-passkey-not-found = The passkey is not registered
+error-passkey-already-registered = The passkey is already registered
+error-email-already-exists = An account with this email already exists
+error-referral-code-invalid = The provided referral code is invalid
+error-registration-closed = Registration is currently closed for new users
+error-invalid-credentials = The provided credentials are invalid
+error-account-locked = The account is temporarily locked due to too many failed signin attempts
+error-signin-too-many-attempts = Too many failed signin attempts, please try again later
+error-two-factor-not-required = The session doesn't require a second authentication factor
+error-recovery-code-invalid = The provided recovery code is invalid or has already been used
+error-session-missing = No session was provided
+error-session-invalid = The provided session is invalid
+error-session-expired = The session has expired
+error-session-unauthorized = The session is not authorized to access this resource
+error-data-export-invalid = The download link is invalid
+error-data-export-expired = The download link has expired, request a new export
+error-data-export-cooldown = A data export was requested recently, wait an hour before requesting another one
+error-password-incorrect = The provided password is incorrect
+error-email-unchanged = The new email is the same as the current one
+error-email-change-not-pending = There is no pending email change, it was either completed or cancelled
+error-email-change-token-invalid = The email change token is invalid
+error-email-change-too-many-attempts = Too many invalid attempts, request the email change again
+error-current-password-incorrect = The current password is incorrect
+error-account-deletion-token-invalid = The cancellation link is invalid or the deletion was already cancelled
+error-locale-unsupported = The locale is not supported, use one of { $locales }
+validation-code-length = The code must be exactly 6 digits long
+validation-code-format = The code must contain only numbers
+validation-password-too-short = The password must be at least { $min } characters long
+validation-password-too-long = The password must be at most { $max } characters long
+validation-password-common = The password is too common
+validation-password-contains-email = The password must not contain the email address
+validation-password-too-weak = The password is too easy to guess, try a longer password or a passphrase
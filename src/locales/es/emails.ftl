@@ -0,0 +1,49 @@
+# Copy of the transactional emails, the layout and markup live in the email templates
+
+email-footer-sent-by = Este correo fue enviado por { $brand }.
+email-footer-support = ¿Preguntas? Escríbenos a { $address }.
+email-code-expiry = El código caduca en { $minutes } minutos.
+email-verification-reference = Referencia de verificación: { $reference }
+email-verification-subject = Activación de la cuenta
+email-verification-intro = ¡Te damos la bienvenida a { $brand }! Usa el siguiente código para activar tu cuenta.
+email-verification-confirmation-subject = Correo electrónico verificado
+email-verification-confirmation-body = Tu correo electrónico se ha verificado, tu cuenta ya está totalmente activada.
+email-password-reset-subject = Restablecer contraseña
+email-password-reset-intro = Se solicitó restablecer la contraseña de tu cuenta. Usa el siguiente enlace para elegir una nueva.
+email-password-reset-button = Restablecer contraseña
+email-password-reset-ignore = Si no solicitaste restablecer la contraseña, puedes ignorar este correo.
+email-password-reset-confirmation-subject = Tu contraseña ha cambiado
+email-password-reset-confirmation-body = Tu contraseña ha cambiado y se cerraron todas tus sesiones. Si no fuiste tú, restablece tu contraseña de inmediato.
+email-password-changed-subject = Tu contraseña ha cambiado
+email-password-changed-body = Tu contraseña ha cambiado y se cerraron todas tus demás sesiones. Si no fuiste tú, restablece tu contraseña de inmediato.
+email-change-verification-subject = Confirma tu nuevo correo electrónico
+email-change-verification-intro = Usa el siguiente código para confirmar esta dirección como el nuevo correo de tu cuenta.
+email-change-requested-subject = Cambio de correo solicitado
+email-change-requested-body = Se solicitó cambiar el correo de tu cuenta a { $new_email }.
+email-change-requested-warning = Si no fuiste tú, cancela el cambio y restablece tu contraseña.
+email-change-requested-button = Cancelar el cambio
+email-changed-subject = Tu correo electrónico ha cambiado
+email-changed-body = El correo de tu cuenta se cambió a { $new_email }. Si no fuiste tú, contacta con soporte de inmediato.
+email-account-deletion-scheduled-subject = Tu cuenta está programada para eliminarse
+email-account-deletion-scheduled-body = Tu cuenta y todos sus datos se eliminarán de forma permanente el { $scheduled_at }.
+email-account-deletion-scheduled-note = Si cambias de opinión, puedes cancelar la eliminación hasta entonces.
+email-account-deletion-scheduled-button = Cancelar la eliminación
+email-account-deleted-subject = Tu cuenta ha sido eliminada
+email-account-deleted-body = Tu cuenta y todos sus datos se han eliminado de forma permanente.
+email-data-export-subject = Tu exportación de datos está lista
+email-data-export-body = La exportación de los datos de tu cuenta está lista.
+email-data-export-button = Descargar
+email-data-export-expiry = El enlace caduca el { $expires_at }.
+email-magic-link-subject = Tu enlace de inicio de sesión
+email-magic-link-intro = Usa el siguiente enlace para iniciar sesión o introduce el código en la página de inicio de sesión.
+email-magic-link-button = Iniciar sesión
+email-magic-link-ignore = Si no intentaste iniciar sesión, puedes ignorar este correo.
+email-recovery-code-used-subject = Código de recuperación utilizado
+email-recovery-code-used-body = Se acaba de usar un código de recuperación para iniciar sesión en tu cuenta. { $remaining ->
+        [one] Te queda 1 código de recuperación.
+       *[other] Te quedan { $remaining } códigos de recuperación.
+    } Si no fuiste tú, restablece tu contraseña de inmediato.
+email-account-locked-subject = Tu cuenta ha sido bloqueada
+email-account-locked-body = Tu cuenta se ha bloqueado durante { $minutes } minutos tras demasiados intentos fallidos de inicio de sesión.
+email-account-locked-hint = Si fuiste tú, puedes desbloquearla ahora mismo. Si no, considera restablecer tu contraseña.
+email-account-locked-button = Desbloquear la cuenta
@@ -0,0 +1,69 @@
+# Messages returned in the `message` field of error responses
+
+error-email-service = Se produjo un error al enviar el correo electrónico
+error-hashing = Se produjo un error al procesar las credenciales
+error-encryption = Se produjo un error al procesar los secretos
+error-unlock-token-invalid = El enlace de desbloqueo no es válido o ya se ha utilizado
+error-unlock-token-expired = El enlace de desbloqueo ha caducado
+error-password-reset-token-expired = El enlace para restablecer la contraseña ha caducado
+error-password-reset-token-invalid = El enlace para restablecer la contraseña no es válido
+error-email-invalid = El correo electrónico proporcionado no es válido
+error-session-not-found = La sesión no existe o no pertenece a esta cuenta
+error-password-breached = La contraseña aparece en una filtración de datos conocida, elige otra
+error-verification-token-expired = El enlace de verificación ha caducado
+error-verification-token-invalid = El enlace de verificación no es válido
+error-email-already-verified = El correo electrónico ya está verificado
+error-verification-code-invalid = El código de verificación no es válido
+error-verification-code-expired = El código de verificación ha caducado
+error-verification-code-used = El código de verificación ya se ha utilizado
+error-verification-too-many-attempts = Demasiados intentos no válidos, solicita un nuevo código de verificación
+error-verification-resend-cooldown = Se acaba de enviar un código de verificación, espera un minuto antes de solicitar otro
+error-verification-resend-limit = Se ha alcanzado el límite diario de correos de verificación
+error-magic-link-invalid = El enlace de inicio de sesión no es válido o ya se ha utilizado
+error-magic-link-expired = El enlace de inicio de sesión ha caducado
+error-magic-link-code-invalid = El código de inicio de sesión no es válido
+error-magic-link-too-many-attempts = Demasiados intentos no válidos, solicita un nuevo enlace de inicio de sesión
+error-magic-link-missing-credentials = Debes proporcionar un token o un correo electrónico y un código
+error-account-not-verified = La cuenta no está verificada
+error-account-pending-deletion = La cuenta está programada para eliminarse, usa el enlace del correo de eliminación para cancelarlo
+error-two-factor-already-enabled = La autenticación en dos pasos ya está activada
+error-two-factor-setup-not-started = La configuración de la autenticación en dos pasos no se ha iniciado
+error-two-factor-not-enabled = La autenticación en dos pasos no está activada
+error-two-factor-code-invalid = El código de autenticación en dos pasos no es válido
+error-two-factor-too-many-attempts = Demasiados códigos no válidos, vuelve a iniciar la configuración de la autenticación en dos pasos
+error-passkey-challenge-invalid = El desafío de la llave de acceso no es válido
+error-passkey-challenge-expired = El desafío de la llave de acceso ha caducado
+error-passkey-verification-failed = No se pudo verificar la llave de acceso
+error-passkey-unsupported = Solo se admiten llaves de acceso ES256
+error-passkey-not-found = La llave de acceso no está registrada
+error-passkey-already-registered = La llave de acceso ya está registrada
+error-email-already-exists = Ya existe una cuenta con este correo electrónico
+error-referral-code-invalid = El código de referido no es válido
+error-registration-closed = El registro está cerrado para nuevos usuarios en este momento
+error-invalid-credentials = Las credenciales no son válidas
+error-account-locked = La cuenta está bloqueada temporalmente por demasiados intentos fallidos de inicio de sesión
+error-signin-too-many-attempts = Demasiados intentos fallidos de inicio de sesión, inténtalo de nuevo más tarde
+error-two-factor-not-required = La sesión no requiere un segundo factor de autenticación
+error-recovery-code-invalid = El código de recuperación no es válido o ya se ha utilizado
+error-session-missing = No se proporcionó ninguna sesión
+error-session-invalid = La sesión no es válida
+error-session-expired = La sesión ha caducado
+error-session-unauthorized = La sesión no está autorizada para acceder a este recurso
+error-data-export-invalid = El enlace de descarga no es válido
+error-data-export-expired = El enlace de descarga ha caducado, solicita una nueva exportación
+error-data-export-cooldown = Se acaba de solicitar una exportación de datos, espera una hora antes de solicitar otra
+error-password-incorrect = La contraseña es incorrecta
+error-email-unchanged = El nuevo correo electrónico es igual al actual
+error-email-change-not-pending = No hay ningún cambio de correo pendiente, se completó o se canceló
+error-email-change-token-invalid = El enlace de cambio de correo no es válido
+error-email-change-too-many-attempts = Demasiados intentos no válidos, solicita de nuevo el cambio de correo
+error-current-password-incorrect = La contraseña actual es incorrecta
+error-account-deletion-token-invalid = El enlace de cancelación no es válido o la eliminación ya se canceló
+error-locale-unsupported = El idioma no es compatible, usa uno de { $locales }
+validation-code-length = El código debe tener exactamente 6 dígitos
+validation-code-format = El código solo puede contener números
+validation-password-too-short = La contraseña debe tener al menos { $min } caracteres
+validation-password-too-long = La contraseña debe tener como máximo { $max } caracteres
+validation-password-common = La contraseña es demasiado común
+validation-password-contains-email = La contraseña no debe contener el correo electrónico
+validation-password-too-weak = La contraseña es demasiado fácil de adivinar, prueba una más larga o una frase de contraseña
@@ -0,0 +1,49 @@
+# Copy of the transactional emails, the layout and markup live in the email templates
+
+email-footer-sent-by = Cet e-mail a été envoyé par { $brand }.
+email-footer-support = Des questions ? Contactez-nous à { $address }.
+email-code-expiry = Le code expire dans { $minutes } minutes.
+email-verification-reference = Référence de vérification : { $reference }
+email-verification-subject = Activation du compte
+email-verification-intro = Bienvenue sur { $brand } ! Utilisez le code ci-dessous pour activer votre compte.
+email-verification-confirmation-subject = Adresse e-mail vérifiée
+email-verification-confirmation-body = Votre adresse e-mail a été vérifiée, votre compte est maintenant entièrement activé.
+email-password-reset-subject = Réinitialisation du mot de passe
+email-password-reset-intro = Une réinitialisation du mot de passe a été demandée pour votre compte. Utilisez le lien ci-dessous pour choisir un nouveau mot de passe.
+email-password-reset-button = Réinitialiser le mot de passe
+email-password-reset-ignore = Si vous n'avez pas demandé de réinitialisation, vous pouvez ignorer cet e-mail.
+email-password-reset-confirmation-subject = Votre mot de passe a été modifié
+email-password-reset-confirmation-body = Votre mot de passe a été modifié et toutes vos sessions ont été déconnectées. Si ce n'était pas vous, réinitialisez immédiatement votre mot de passe.
+email-password-changed-subject = Votre mot de passe a été modifié
+email-password-changed-body = Votre mot de passe a été modifié et toutes vos autres sessions ont été déconnectées. Si ce n'était pas vous, réinitialisez immédiatement votre mot de passe.
+email-change-verification-subject = Confirmez votre nouvelle adresse e-mail
+email-change-verification-intro = Utilisez le code ci-dessous pour confirmer cette adresse comme nouvelle adresse e-mail de votre compte.
+email-change-requested-subject = Changement d'adresse e-mail demandé
+email-change-requested-body = Une demande a été faite pour changer l'adresse e-mail de votre compte en { $new_email }.
+email-change-requested-warning = Si ce n'était pas vous, annulez le changement et réinitialisez votre mot de passe.
+email-change-requested-button = Annuler le changement
+email-changed-subject = Votre adresse e-mail a été modifiée
+email-changed-body = L'adresse e-mail de votre compte a été changée en { $new_email }. Si ce n'était pas vous, contactez immédiatement le support.
+email-account-deletion-scheduled-subject = Votre compte va être supprimé
+email-account-deletion-scheduled-body = Votre compte et toutes ses données seront définitivement supprimés le { $scheduled_at }.
+email-account-deletion-scheduled-note = Si vous changez d'avis, vous pouvez annuler la suppression d'ici là.
+email-account-deletion-scheduled-button = Annuler la suppression
+email-account-deleted-subject = Votre compte a été supprimé
+email-account-deleted-body = Votre compte et toutes ses données ont été définitivement supprimés.
+email-data-export-subject = Votre export de données est prêt
+email-data-export-body = L'export des données de votre compte est prêt.
+email-data-export-button = Télécharger
+email-data-export-expiry = Le lien expire le { $expires_at }.
+email-magic-link-subject = Votre lien de connexion
+email-magic-link-intro = Utilisez le lien ci-dessous pour vous connecter, ou saisissez le code sur la page de connexion.
+email-magic-link-button = Se connecter
+email-magic-link-ignore = Si vous n'avez pas essayé de vous connecter, vous pouvez ignorer cet e-mail.
+email-recovery-code-used-subject = Code de récupération utilisé
+email-recovery-code-used-body = Un code de récupération vient d'être utilisé pour vous connecter à votre compte. { $remaining ->
+        [one] Il vous reste 1 code de récupération.
+       *[other] Il vous reste { $remaining } codes de récupération.
+    } Si ce n'était pas vous, réinitialisez immédiatement votre mot de passe.
+email-account-locked-subject = Votre compte a été verrouillé
+email-account-locked-body = Votre compte a été verrouillé pendant { $minutes } minutes après trop de tentatives de connexion échouées.
+email-account-locked-hint = Si c'était vous, vous pouvez le déverrouiller immédiatement. Sinon, pensez à réinitialiser votre mot de passe.
+email-account-locked-button = Déverrouiller le compte
@@ -0,0 +1,69 @@
+# Messages returned in the `message` field of error responses
+
+error-email-service = Une erreur s'est produite lors de l'envoi de l'e-mail
+error-hashing = Une erreur s'est produite lors du traitement des identifiants
+error-encryption = Une erreur s'est produite lors du traitement des secrets
+error-unlock-token-invalid = Le lien de déverrouillage est invalide ou a déjà été utilisé
+error-unlock-token-expired = Le lien de déverrouillage a expiré
+error-password-reset-token-expired = Le lien de réinitialisation du mot de passe a expiré
+error-password-reset-token-invalid = Le lien de réinitialisation du mot de passe est invalide
+error-email-invalid = L'adresse e-mail fournie est invalide
+error-session-not-found = La session n'existe pas ou n'appartient pas à ce compte
+error-password-breached = Le mot de passe figure dans une fuite de données connue, veuillez en choisir un autre
+error-verification-token-expired = Le lien de vérification a expiré
+error-verification-token-invalid = Le lien de vérification est invalide
+error-email-already-verified = L'adresse e-mail est déjà vérifiée
+error-verification-code-invalid = Le code de vérification est invalide
+error-verification-code-expired = Le code de vérification a expiré
+error-verification-code-used = Le code de vérification a déjà été utilisé
+error-verification-too-many-attempts = Trop de tentatives invalides, demandez un nouveau code de vérification
+error-verification-resend-cooldown = Un code de vérification vient d'être envoyé, attendez une minute avant d'en demander un autre
+error-verification-resend-limit = La limite quotidienne d'e-mails de vérification a été atteinte
+error-magic-link-invalid = Le lien de connexion est invalide ou a déjà été utilisé
+error-magic-link-expired = Le lien de connexion a expiré
+error-magic-link-code-invalid = Le code de connexion est invalide
+error-magic-link-too-many-attempts = Trop de tentatives invalides, demandez un nouveau lien de connexion
+error-magic-link-missing-credentials = Il faut fournir soit un jeton, soit une adresse e-mail et un code
+error-account-not-verified = Le compte n'est pas vérifié
+error-account-pending-deletion = Le compte doit être supprimé, utilisez le lien de l'e-mail de suppression pour l'annuler
+error-two-factor-already-enabled = L'authentification à deux facteurs est déjà activée
+error-two-factor-setup-not-started = La configuration de l'authentification à deux facteurs n'a pas été commencée
+error-two-factor-not-enabled = L'authentification à deux facteurs n'est pas activée
+error-two-factor-code-invalid = Le code d'authentification à deux facteurs est invalide
+error-two-factor-too-many-attempts = Trop de codes invalides, recommencez la configuration de l'authentification à deux facteurs
+error-passkey-challenge-invalid = Le défi de la clé d'accès est invalide
+error-passkey-challenge-expired = Le défi de la clé d'accès a expiré
+error-passkey-verification-failed = La clé d'accès n'a pas pu être vérifiée
+error-passkey-unsupported = Seules les clés d'accès ES256 sont prises en charge
+error-passkey-not-found = La clé d'accès n'est pas enregistrée
+error-passkey-already-registered = La clé d'accès est déjà enregistrée
+error-email-already-exists = Un compte avec cette adresse e-mail existe déjà
+error-referral-code-invalid = Le code de parrainage est invalide
+error-registration-closed = Les inscriptions sont actuellement fermées aux nouveaux utilisateurs
+error-invalid-credentials = Les identifiants sont invalides
+error-account-locked = Le compte est temporairement verrouillé après trop de tentatives de connexion échouées
+error-signin-too-many-attempts = Trop de tentatives de connexion échouées, veuillez réessayer plus tard
+error-two-factor-not-required = La session ne nécessite pas de second facteur d'authentification
+error-recovery-code-invalid = Le code de récupération est invalide ou a déjà été utilisé
+error-session-missing = Aucune session n'a été fournie
+error-session-invalid = La session est invalide
+error-session-expired = La session a expiré
+error-session-unauthorized = La session n'est pas autorisée à accéder à cette ressource
+error-data-export-invalid = Le lien de téléchargement est invalide
+error-data-export-expired = Le lien de téléchargement a expiré, demandez un nouvel export
+error-data-export-cooldown = Un export des données vient d'être demandé, attendez une heure avant d'en demander un autre
+error-password-incorrect = Le mot de passe est incorrect
+error-email-unchanged = La nouvelle adresse e-mail est identique à l'actuelle
+error-email-change-not-pending = Aucun changement d'adresse e-mail n'est en attente, il a été finalisé ou annulé
+error-email-change-token-invalid = Le lien de changement d'adresse e-mail est invalide
+error-email-change-too-many-attempts = Trop de tentatives invalides, demandez à nouveau le changement d'adresse e-mail
+error-current-password-incorrect = Le mot de passe actuel est incorrect
+error-account-deletion-token-invalid = Le lien d'annulation est invalide ou la suppression a déjà été annulée
+error-locale-unsupported = La langue n'est pas prise en charge, utilisez l'une de { $locales }
+validation-code-length = Le code doit comporter exactement 6 chiffres
+validation-code-format = Le code ne doit contenir que des chiffres
+validation-password-too-short = Le mot de passe doit comporter au moins { $min } caractères
+validation-password-too-long = Le mot de passe doit comporter au plus { $max } caractères
+validation-password-common = Le mot de passe est trop courant
+validation-password-contains-email = Le mot de passe ne doit pas contenir l'adresse e-mail
+validation-password-too-weak = Le mot de passe est trop facile à deviner, essayez un mot de passe plus long ou une phrase de passe
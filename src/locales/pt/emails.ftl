@@ -0,0 +1,49 @@
+# Copy of the transactional emails, the layout and markup live in the email templates
+
+email-footer-sent-by = Este e-mail foi enviado por { $brand }.
+email-footer-support = Dúvidas? Fale conosco em { $address }.
+email-code-expiry = O código expira em { $minutes } minutos.
+email-verification-reference = Referência de verificação: { $reference }
+email-verification-subject = Ativação da conta
+email-verification-intro = Boas-vindas ao { $brand }! Use o código abaixo para ativar sua conta.
+email-verification-confirmation-subject = E-mail verificado com sucesso
+email-verification-confirmation-body = Seu e-mail foi verificado e sua conta agora está totalmente ativada.
+email-password-reset-subject = Redefinição de senha
+email-password-reset-intro = Foi solicitada a redefinição da senha da sua conta. Use o link abaixo para escolher uma nova senha.
+email-password-reset-button = Redefinir senha
+email-password-reset-ignore = Se você não solicitou a redefinição, pode ignorar este e-mail.
+email-password-reset-confirmation-subject = Sua senha foi alterada
+email-password-reset-confirmation-body = Sua senha foi alterada e todas as suas sessões foram encerradas. Se não foi você, redefina sua senha imediatamente.
+email-password-changed-subject = Sua senha foi alterada
+email-password-changed-body = Sua senha foi alterada e todas as suas outras sessões foram encerradas. Se não foi você, redefina sua senha imediatamente.
+email-change-verification-subject = Confirme seu novo e-mail
+email-change-verification-intro = Use o código abaixo para confirmar este endereço como o novo e-mail da sua conta.
+email-change-requested-subject = Alteração de e-mail solicitada
+email-change-requested-body = Foi solicitado alterar o e-mail da sua conta para { $new_email }.
+email-change-requested-warning = Se não foi você, cancele a alteração e redefina sua senha.
+email-change-requested-button = Cancelar alteração
+email-changed-subject = Seu e-mail foi alterado
+email-changed-body = O e-mail da sua conta foi alterado para { $new_email }. Se não foi você, entre em contato com o suporte imediatamente.
+email-account-deletion-scheduled-subject = Sua conta está programada para exclusão
+email-account-deletion-scheduled-body = Sua conta e todos os seus dados serão excluídos permanentemente em { $scheduled_at }.
+email-account-deletion-scheduled-note = Se mudar de ideia, você pode cancelar a exclusão até lá.
+email-account-deletion-scheduled-button = Cancelar exclusão
+email-account-deleted-subject = Sua conta foi excluída
+email-account-deleted-body = Sua conta e todos os seus dados foram excluídos permanentemente.
+email-data-export-subject = Sua exportação de dados está pronta
+email-data-export-body = A exportação dos dados da sua conta está pronta.
+email-data-export-button = Baixar
+email-data-export-expiry = O link expira em { $expires_at }.
+email-magic-link-subject = Seu link de acesso
+email-magic-link-intro = Use o link abaixo para entrar ou informe o código na página de acesso.
+email-magic-link-button = Entrar
+email-magic-link-ignore = Se você não tentou entrar, pode ignorar este e-mail.
+email-recovery-code-used-subject = Código de recuperação utilizado
+email-recovery-code-used-body = Um código de recuperação acabou de ser usado para entrar na sua conta. { $remaining ->
+        [one] Resta 1 código de recuperação.
+       *[other] Restam { $remaining } códigos de recuperação.
+    } Se não foi você, redefina sua senha imediatamente.
+email-account-locked-subject = Sua conta foi bloqueada
+email-account-locked-body = Sua conta foi bloqueada por { $minutes } minutos após muitas tentativas de acesso malsucedidas.
+email-account-locked-hint = Se foi você, pode desbloqueá-la agora mesmo. Caso contrário, considere redefinir sua senha.
+email-account-locked-button = Desbloquear conta
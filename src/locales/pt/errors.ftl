@@ -0,0 +1,69 @@
+# Messages returned in the `message` field of error responses
+
+error-email-service = Ocorreu um erro ao enviar o e-mail
+error-hashing = Ocorreu um erro ao processar as credenciais
+error-encryption = Ocorreu um erro ao processar os segredos
+error-unlock-token-invalid = O link de desbloqueio é inválido ou já foi utilizado
+error-unlock-token-expired = O link de desbloqueio expirou
+error-password-reset-token-expired = O link de redefinição de senha expirou
+error-password-reset-token-invalid = O link de redefinição de senha é inválido
+error-email-invalid = O e-mail informado é inválido
+error-session-not-found = A sessão não existe ou não pertence a esta conta
+error-password-breached = A senha apareceu em um vazamento de dados conhecido, escolha outra
+error-verification-token-expired = O link de verificação expirou
+error-verification-token-invalid = O link de verificação é inválido
+error-email-already-verified = O e-mail já está verificado
+error-verification-code-invalid = O código de verificação é inválido
+error-verification-code-expired = O código de verificação expirou
+error-verification-code-used = O código de verificação já foi utilizado
+error-verification-too-many-attempts = Muitas tentativas inválidas, solicite um novo código de verificação
+error-verification-resend-cooldown = Um código de verificação foi enviado há pouco, aguarde um minuto antes de solicitar outro
+error-verification-resend-limit = O limite diário de e-mails de verificação foi atingido
+error-magic-link-invalid = O link de acesso é inválido ou já foi utilizado
+error-magic-link-expired = O link de acesso expirou
+error-magic-link-code-invalid = O código de acesso é inválido
+error-magic-link-too-many-attempts = Muitas tentativas inválidas, solicite um novo link de acesso
+error-magic-link-missing-credentials = É necessário informar um token ou um e-mail e um código
+error-account-not-verified = A conta não está verificada
+error-account-pending-deletion = A conta está programada para exclusão, use o link do e-mail de exclusão para cancelá-la
+error-two-factor-already-enabled = A autenticação em dois fatores já está ativada
+error-two-factor-setup-not-started = A configuração da autenticação em dois fatores não foi iniciada
+error-two-factor-not-enabled = A autenticação em dois fatores não está ativada
+error-two-factor-code-invalid = O código de autenticação em dois fatores é inválido
+error-two-factor-too-many-attempts = Muitos códigos inválidos, inicie novamente a configuração da autenticação em dois fatores
+error-passkey-challenge-invalid = O desafio da chave de acesso é inválido
+error-passkey-challenge-expired = O desafio da chave de acesso expirou
+error-passkey-verification-failed = Não foi possível verificar a chave de acesso
+error-passkey-unsupported = Somente chaves de acesso ES256 são suportadas
+error-passkey-not-found = A chave de acesso não está registrada
+error-passkey-already-registered = A chave de acesso já está registrada
+error-email-already-exists = Já existe uma conta com este e-mail
+error-referral-code-invalid = O código de indicação é inválido
+error-registration-closed = O cadastro está fechado para novos usuários no momento
+error-invalid-credentials = As credenciais são inválidas
+error-account-locked = A conta está temporariamente bloqueada após muitas tentativas de acesso malsucedidas
+error-signin-too-many-attempts = Muitas tentativas de acesso malsucedidas, tente novamente mais tarde
+error-two-factor-not-required = A sessão não exige um segundo fator de autenticação
+error-recovery-code-invalid = O código de recuperação é inválido ou já foi utilizado
+error-session-missing = Nenhuma sessão foi informada
+error-session-invalid = A sessão é inválida
+error-session-expired = A sessão expirou
+error-session-unauthorized = A sessão não tem permissão para acessar este recurso
+error-data-export-invalid = O link de download é inválido
+error-data-export-expired = O link de download expirou, solicite uma nova exportação
+error-data-export-cooldown = Uma exportação de dados foi solicitada há pouco, aguarde uma hora antes de solicitar outra
+error-password-incorrect = A senha está incorreta
+error-email-unchanged = O novo e-mail é igual ao atual
+error-email-change-not-pending = Não há alteração de e-mail pendente, ela foi concluída ou cancelada
+error-email-change-token-invalid = O link de alteração de e-mail é inválido
+error-email-change-too-many-attempts = Muitas tentativas inválidas, solicite a alteração de e-mail novamente
+error-current-password-incorrect = A senha atual está incorreta
+error-account-deletion-token-invalid = O link de cancelamento é inválido ou a exclusão já foi cancelada
+error-locale-unsupported = O idioma não é suportado, use um de { $locales }
+validation-code-length = O código deve ter exatamente 6 dígitos
+validation-code-format = O código deve conter apenas números
+validation-password-too-short = A senha deve ter pelo menos { $min } caracteres
+validation-password-too-long = A senha deve ter no máximo { $max } caracteres
+validation-password-common = A senha é muito comum
+validation-password-contains-email = A senha não pode conter o endereço de e-mail
+validation-password-too-weak = A senha é fácil demais de adivinhar, tente uma senha mais longa ou uma frase secreta
@@ -1,20 +1,37 @@
 mod errors;
+mod extractors;
 mod routes;
 mod services;
 mod setup;
+mod tasks;
 mod utils;
 
+use std::net::SocketAddr;
+
 use dotenv::dotenv;
 
 #[tokio::main]
 async fn main() -> surrealdb::Result<()> {
     dotenv().ok();
 
+    services::i18n::load_translations();
+
+    let config = setup::setup_config();
+    let breached_passwords = setup::setup_breached_password_screening();
     let database = setup::setup_database().await?;
-    let email = setup::setup_email_service();
-    let (app, listener) = setup::setup_api_router(database, email).await?;
+    let email = setup::setup_email_service(database.clone());
+
+    tasks::spawn_background_tasks(database.clone(), email.clone());
+
+    let (app, listener) =
+        setup::setup_api_router(config, breached_passwords, database, email).await?;
 
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 
     Ok(())
 }
@@ -0,0 +1,99 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{Duration, Utc};
+use hyper::{header::SET_COOKIE, StatusCode};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::AccountDeletionError, response::ApiError},
+    extractors::AuthSession,
+    services::{database::DatabaseLayer, email::EmailLayer},
+    setup::AppState,
+    utils::{
+        cookies::remove_session_cookie,
+        crypto::{generate_token, verify_password_hash},
+    },
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    scheduled_at: String,
+}
+
+// The account is only marked for deletion, `tasks::account_deletion` removes it once the grace
+// period is over
+#[axum::debug_handler]
+pub async fn account_deletion(
+    State(app_state): State<AppState>,
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Response), ApiError<AccountDeletionError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        password: payload.password.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Verify the password
+
+    let password_matches =
+        verify_password_hash(payload.password.clone(), user.password_hash.clone()).await?;
+
+    if !password_matches {
+        return Err(ApiError(AccountDeletionError::InvalidPassword));
+    }
+    println!("2. Password confirmed successfully!");
+
+    // 3. Schedule the deletion, this also revokes all user sessions
+
+    let scheduled_at = Utc::now() + Duration::days(app_state.config.account_deletion_grace_days);
+    let cancel_token = generate_token();
+
+    database_layer
+        .query()
+        .account_deletion
+        .schedule(&cancel_token, user.id.clone(), scheduled_at)
+        .await?;
+    println!("3. Account deletion scheduled successfully!");
+
+    // 4. Send the cancellation link
+
+    email_layer
+        .send_account_deletion_scheduled(user.email.clone(), scheduled_at, cancel_token)
+        .await?;
+    println!("4. Account deletion email sent successfully!");
+
+    // 5. Expire the session cookie
+
+    let cookie = remove_session_cookie();
+
+    let mut response = (
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Account scheduled for deletion"),
+            scheduled_at: scheduled_at.to_rfc3339(),
+        }),
+    )
+        .into_response();
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+
+    Ok((StatusCode::OK, response))
+}
@@ -0,0 +1,62 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::AccountDeletionError, response::ApiError, CommonError},
+    services::database::DatabaseLayer,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+// Used through the emailed link, the sessions were revoked when the deletion was requested
+#[axum::debug_handler]
+pub async fn account_deletion_cancel(
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<AccountDeletionError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        token: payload.token.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Cancel the scheduled deletion
+
+    match database_layer
+        .query()
+        .account_deletion
+        .cancel(&payload.token)
+        .await
+    {
+        Ok(_user_id) => (),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(AccountDeletionError::InvalidToken));
+        }
+        Err(err) => {
+            return Err(ApiError(AccountDeletionError::Common(
+                CommonError::Database(err),
+            )))
+        }
+    };
+    println!("2. Account deletion cancelled successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Account deletion cancelled successfully"),
+        }),
+    ))
+}
@@ -0,0 +1,351 @@
+use axum::{Extension, Json};
+use chrono::{DateTime, Duration, Utc};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use surrealdb::sql::Datetime;
+
+use crate::{
+    errors::{account::DataExportError, response::ApiError, CommonError},
+    extractors::AuthSession,
+    services::{
+        database::{
+            email_outbox::OutboxEmail, email_verification::EmailVerification,
+            failed_signin::FailedSignin, passkey::Passkey,
+            password_reset_request::PasswordResetRequest, recovery_code::RecoveryCode,
+            session::Session, user::User, DatabaseLayer,
+        },
+        email::EmailLayer,
+    },
+    utils::crypto::generate_token,
+};
+
+// Every export builds and stores a full archive and sends an email, so they're limited per user
+const EXPORT_COOLDOWN_MINUTES: i64 = 60;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn data_export(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<DataExportError>> {
+    // 1. Assemble the archive of everything stored about the user
+
+    let archive_data = load_archive_data(&database_layer, &user).await?;
+    let archive = build_archive(&user, &archive_data, Utc::now());
+    println!("1. Data export archive assembled successfully!");
+
+    // 2. Store the archive behind a download token, unless an export was requested recently
+
+    let token = generate_token();
+
+    let archive =
+        serde_json::to_string_pretty(&archive).expect("Serializing a JSON value can't fail");
+
+    let expires_at = database_layer
+        .query()
+        .data_export
+        .create(
+            &token,
+            user.id.clone(),
+            archive,
+            Duration::minutes(EXPORT_COOLDOWN_MINUTES),
+        )
+        .await?
+        .ok_or(ApiError(DataExportError::ExportCooldown))?;
+    println!("2. Data export stored successfully!");
+
+    // 3. Send the download link
+
+    email_layer
+        .send_data_export(user.email.clone(), token, expires_at)
+        .await?;
+    println!("3. Data export email sent successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("A download link for the data export was sent to your email"),
+        }),
+    ))
+}
+
+// Everything stored about the user, loaded up front so the archive itself can be built without
+// the database
+struct ArchiveData {
+    sessions: Vec<Session>,
+    email_verifications: Vec<EmailVerification>,
+    email_verification_dispatches: Vec<Datetime>,
+    magic_link_dispatches: Vec<Datetime>,
+    password_reset_request: Option<PasswordResetRequest>,
+    passkeys: Vec<Passkey>,
+    recovery_codes: Vec<RecoveryCode>,
+    failed_signins: Vec<FailedSignin>,
+    outbox_emails: Vec<OutboxEmail>,
+}
+
+async fn load_archive_data(
+    database_layer: &DatabaseLayer,
+    user: &User,
+) -> Result<ArchiveData, DataExportError> {
+    let query = database_layer.query();
+
+    let into_error = |err| DataExportError::Common(CommonError::Database(err));
+
+    let sessions = query
+        .session
+        .list(user.id.clone())
+        .await
+        .map_err(into_error)?;
+    let email_verifications = query
+        .email_verification
+        .list(user.id.clone())
+        .await
+        .map_err(into_error)?;
+    let email_verification_dispatches = query
+        .email_verification
+        .dispatches_since(user.id.clone(), DateTime::<Utc>::UNIX_EPOCH)
+        .await
+        .map_err(into_error)?;
+    let magic_link_dispatches = query
+        .magic_link
+        .dispatches_since(user.id.clone(), DateTime::<Utc>::UNIX_EPOCH)
+        .await
+        .map_err(into_error)?;
+    let password_reset_request = match query.password_reset_request.get(user.id.clone()).await {
+        Ok(password_reset_request) => Some(password_reset_request),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => None,
+        Err(err) => return Err(into_error(err)),
+    };
+    let passkeys = query
+        .passkey
+        .list(user.id.clone())
+        .await
+        .map_err(into_error)?;
+    let recovery_codes = query
+        .recovery_code
+        .list(user.id.clone())
+        .await
+        .map_err(into_error)?;
+    let failed_signins = query
+        .failed_signin
+        .list_for_user(user.id.clone())
+        .await
+        .map_err(into_error)?;
+    let outbox_emails = query
+        .email_outbox
+        .list_for_user(user.id.clone())
+        .await
+        .map_err(into_error)?;
+
+    Ok(ArchiveData {
+        sessions,
+        email_verifications,
+        email_verification_dispatches,
+        magic_link_dispatches,
+        password_reset_request,
+        passkeys,
+        recovery_codes,
+        failed_signins,
+        outbox_emails,
+    })
+}
+
+// Secrets (password and code hashes, the TOTP secret, passkey public keys) are left out, they
+// aren't personal data and would only weaken the account if the archive leaked
+fn build_archive(user: &User, data: &ArchiveData, exported_at: DateTime<Utc>) -> Value {
+    json!({
+        "exported_at": exported_at.to_rfc3339(),
+        "user": {
+            "id": user.id.to_raw(),
+            "email": user.email,
+            "email_verified": user.email_verified,
+            "created_at": format_datetime(&user.created_at),
+            "locale": user.locale,
+            "totp_enabled": user.totp_enabled,
+            "locked_until": user.locked_until.as_ref().map(format_datetime),
+            "lock_count": user.lock_count,
+            "deletion_scheduled_at": user.deletion_scheduled_at.as_ref().map(format_datetime),
+        },
+        "sessions": data.sessions.iter().map(|session| json!({
+            "authorized": session.authorized,
+            "created_at": format_datetime(&session.created_at),
+            "expires_at": format_datetime(&session.expires_at),
+            "last_accessed_at": format_datetime(&session.last_accessed_at),
+            "user_agent": session.user_agent,
+            "ip_address": session.ip_address,
+            "device_label": session.device_label,
+        })).collect::<Vec<Value>>(),
+        "email_verifications": data.email_verifications.iter().map(|email_verification| json!({
+            "purpose": email_verification.purpose,
+            "new_email": email_verification.new_email,
+            "attempts": email_verification.attempts,
+            "created_at": format_datetime(&email_verification.created_at),
+            "expires_at": format_datetime(&email_verification.expires_at),
+        })).collect::<Vec<Value>>(),
+        "email_verification_emails_sent_at": data.email_verification_dispatches
+            .iter()
+            .map(format_datetime)
+            .collect::<Vec<String>>(),
+        "magic_link_emails_sent_at": data.magic_link_dispatches
+            .iter()
+            .map(format_datetime)
+            .collect::<Vec<String>>(),
+        "password_reset_requests": data.password_reset_request.iter().map(|password_reset_request| json!({
+            "created_at": format_datetime(&password_reset_request.created_at),
+            "expires_at": format_datetime(&password_reset_request.expires_at),
+        })).collect::<Vec<Value>>(),
+        "passkeys": data.passkeys.iter().map(|passkey| json!({
+            "name": passkey.name,
+            "created_at": format_datetime(&passkey.created_at),
+            "last_used_at": passkey.last_used_at.as_ref().map(format_datetime),
+        })).collect::<Vec<Value>>(),
+        "emails": data.outbox_emails.iter().map(|email| json!({
+            "recipient": email.recipient,
+            "subject": email.subject,
+            "status": email.status,
+            "created_at": format_datetime(&email.created_at),
+            "sent_at": email.sent_at.as_ref().map(format_datetime),
+        })).collect::<Vec<Value>>(),
+        "remaining_recovery_codes": data.recovery_codes.len(),
+        "audit_events": data.failed_signins.iter().map(|failed_signin| json!({
+            "event": "failed_signin",
+            "ip_address": failed_signin.ip_address,
+            "created_at": format_datetime(&failed_signin.created_at),
+        })).collect::<Vec<Value>>(),
+    })
+}
+
+fn format_datetime(datetime: &Datetime) -> String {
+    datetime.to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::sql::Thing;
+
+    use super::*;
+
+    const SECRET: &str = "do-not-export";
+
+    fn thing(table: &str) -> Thing {
+        Thing::from((table.to_string(), "1".to_string()))
+    }
+
+    fn user() -> User {
+        let mut user = User::new(
+            thing("user"),
+            String::from("jane@example.com"),
+            format!("{}-password-hash", SECRET),
+            Datetime::default(),
+        );
+        user.totp_secret = Some(format!("{}-totp-secret", SECRET));
+        user.totp_enabled = true;
+        user.locale = Some(String::from("de"));
+
+        user
+    }
+
+    fn archive_data() -> ArchiveData {
+        ArchiveData {
+            sessions: vec![Session {
+                id: thing("session"),
+                authorized: true,
+                created_at: Datetime::default(),
+                expires_at: Datetime::default(),
+                last_accessed_at: Datetime::default(),
+                user_agent: Some(String::from("Firefox")),
+                ip_address: Some(String::from("203.0.113.7")),
+                device_label: None,
+                second_factor_attempts: 0,
+                user: thing("user"),
+            }],
+            email_verifications: vec![EmailVerification {
+                id: thing("email_verification"),
+                code: format!("{}-code-hash", SECRET),
+                attempts: 1,
+                purpose: String::from("email_change"),
+                new_email: Some(String::from("jane@example.org")),
+                cancel_token_hash: Some(format!("{}-cancel-token-hash", SECRET)),
+                created_at: Datetime::default(),
+                expires_at: Datetime::default(),
+                user: thing("user"),
+            }],
+            email_verification_dispatches: vec![Datetime::default()],
+            magic_link_dispatches: vec![],
+            password_reset_request: Some(PasswordResetRequest {
+                id: thing("password_reset_request"),
+                created_at: Datetime::default(),
+                expires_at: Datetime::default(),
+                user: thing("user"),
+            }),
+            passkeys: vec![Passkey {
+                id: thing("passkey"),
+                credential_id: format!("{}-credential-id", SECRET),
+                public_key: format!("{}-public-key", SECRET),
+                sign_count: 3,
+                name: Some(String::from("Laptop")),
+                created_at: Datetime::default(),
+                last_used_at: None,
+                user: thing("user"),
+            }],
+            recovery_codes: vec![RecoveryCode {
+                id: thing("recovery_code"),
+                code_hash: format!("{}-recovery-code-hash", SECRET),
+                created_at: Datetime::default(),
+                user: thing("user"),
+            }],
+            failed_signins: vec![FailedSignin {
+                id: thing("failed_signin"),
+                email: String::from("jane@example.com"),
+                ip_address: Some(String::from("198.51.100.4")),
+                created_at: Datetime::default(),
+                user: Some(thing("user")),
+            }],
+            outbox_emails: vec![OutboxEmail {
+                id: thing("email_outbox"),
+                sender: String::from("Orvane <noreply@orvane.test>"),
+                recipient: String::from("jane@example.com"),
+                subject: String::from("Orvane - Account Activation"),
+                html: format!("<p>Your code is {}</p>", SECRET),
+                text: Some(format!("Your code is {}", SECRET)),
+                status: String::from("pending"),
+                attempts: 0,
+                last_error: None,
+                created_at: Datetime::default(),
+                next_attempt_at: Datetime::default(),
+                sent_at: None,
+                user: Some(thing("user")),
+            }],
+        }
+    }
+
+    #[test]
+    fn archive_leaves_out_secrets() {
+        let archive = build_archive(&user(), &archive_data(), Utc::now()).to_string();
+
+        assert!(!archive.contains(SECRET));
+    }
+
+    #[test]
+    fn archive_contains_personal_data() {
+        let archive = build_archive(&user(), &archive_data(), Utc::now());
+
+        assert_eq!(archive["user"]["email"], "jane@example.com");
+        assert_eq!(archive["user"]["locale"], "de");
+        assert_eq!(archive["user"]["totp_enabled"], true);
+        assert_eq!(archive["sessions"][0]["ip_address"], "203.0.113.7");
+        assert_eq!(
+            archive["email_verifications"][0]["new_email"],
+            "jane@example.org"
+        );
+        assert_eq!(archive["passkeys"][0]["name"], "Laptop");
+        assert_eq!(archive["remaining_recovery_codes"], 1);
+        assert_eq!(archive["audit_events"][0]["ip_address"], "198.51.100.4");
+    }
+}
@@ -0,0 +1,54 @@
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use chrono::Utc;
+use hyper::{
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    StatusCode,
+};
+
+use crate::{
+    errors::{account::DataExportError, response::ApiError, CommonError},
+    services::database::DatabaseLayer,
+};
+
+// Opened straight from the emailed link, the token is the only credential
+#[axum::debug_handler]
+pub async fn data_export_download(
+    Path(token): Path<String>,
+    Extension(database_layer): Extension<DatabaseLayer>,
+) -> Result<Response, ApiError<DataExportError>> {
+    // 1. Retrieve the data export and check its expiration
+
+    let data_export = match database_layer.query().data_export.get(&token).await {
+        Ok(data_export) => data_export,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(DataExportError::InvalidToken));
+        }
+        Err(err) => {
+            return Err(ApiError(DataExportError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+
+    if *data_export.expires_at < Utc::now() {
+        return Err(ApiError(DataExportError::TokenExpired));
+    }
+    println!("1. Data export retrieved successfully!");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, "application/json"),
+            (
+                CONTENT_DISPOSITION,
+                "attachment; filename=\"orvane-data-export.json\"",
+            ),
+        ],
+        data_export.archive,
+    )
+        .into_response())
+}
@@ -0,0 +1,113 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::EmailChangeError, response::ApiError},
+    extractors::AuthSession,
+    services::{database::DatabaseLayer, email::EmailLayer},
+    utils::{
+        crypto::{generate_token, hash_string, verify_password_hash},
+        random::generate_random_code,
+        validation::normalize_email,
+    },
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(email)]
+    new_email: String,
+    #[validate(length(min = 1))]
+    password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+// First step of the email change, `user.email` only changes once `email_change_confirm` succeeds
+#[axum::debug_handler]
+pub async fn email_change(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(mut payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<EmailChangeError>> {
+    // 1. Validate payload input
+    payload.new_email = normalize_email(&payload.new_email);
+
+    let payload_instance = RoutePayload {
+        new_email: payload.new_email.clone(),
+        password: payload.password.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Verify the password
+
+    let password_matches =
+        verify_password_hash(payload.password.clone(), user.password_hash.clone()).await?;
+
+    if !password_matches {
+        return Err(ApiError(EmailChangeError::InvalidPassword));
+    }
+    println!("2. Password confirmed successfully!");
+
+    // 3. Check if the new email is available
+
+    if payload.new_email == user.email {
+        return Err(ApiError(EmailChangeError::SameEmail));
+    }
+
+    let user_exists = database_layer
+        .query()
+        .user
+        .check_if_exists(payload.new_email.clone())
+        .await?;
+
+    if user_exists {
+        return Err(ApiError(EmailChangeError::EmailAlreadyExists));
+    }
+    println!("3. Email availability check completed successfully!");
+
+    // 4. Create the email change verification, replacing any pending change
+
+    let verification_code = generate_random_code(6);
+    let verification_code_hash = hash_string(verification_code.clone());
+    let cancel_token = generate_token();
+
+    let email_verification = database_layer
+        .query()
+        .email_verification
+        .create_email_change(
+            verification_code_hash,
+            user.id.clone(),
+            payload.new_email.clone(),
+            &cancel_token,
+        )
+        .await?;
+    println!("4. Email change verification created successfully!");
+
+    // 5. Send the code to the new address and the cancel link to the current one
+
+    let token_hash = hash_string(email_verification.id.id.to_string());
+
+    email_layer
+        .send_email_change_verification(payload.new_email.clone(), verification_code, token_hash)
+        .await?;
+
+    email_layer
+        .send_email_change_requested(user.email.clone(), payload.new_email, cancel_token)
+        .await?;
+    println!("5. Email change emails sent successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Verification code sent to the new email"),
+        }),
+    ))
+}
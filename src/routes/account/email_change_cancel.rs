@@ -0,0 +1,88 @@
+use axum::{Extension, Json};
+use chrono::Utc;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::EmailChangeError, response::ApiError, CommonError},
+    services::database::DatabaseLayer,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+// Used through the link sent to the current address, so it doesn't require a session
+#[axum::debug_handler]
+pub async fn email_change_cancel(
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<EmailChangeError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        token: payload.token.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Retrieve the pending email change
+
+    let email_verification = match database_layer
+        .query()
+        .email_verification
+        .get_by_cancel_token(&payload.token)
+        .await
+    {
+        Ok(email_verification) => email_verification,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(EmailChangeError::NoPendingChange));
+        }
+        Err(err) => {
+            return Err(ApiError(EmailChangeError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+
+    // An expired change can't be confirmed anymore, so there's nothing left to cancel
+    if *email_verification.expires_at < Utc::now() {
+        return Err(ApiError(EmailChangeError::NoPendingChange));
+    }
+    println!("2. Pending email change retrieved successfully!");
+
+    // 3. Remove the email change
+
+    match database_layer
+        .query()
+        .email_verification
+        .remove(email_verification.id)
+        .await
+    {
+        Ok(()) => (),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidRequest(_))) => {
+            return Err(ApiError(EmailChangeError::NoPendingChange));
+        }
+        Err(err) => {
+            return Err(ApiError(EmailChangeError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+    println!("3. Email change cancelled successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Email change cancelled successfully"),
+        }),
+    ))
+}
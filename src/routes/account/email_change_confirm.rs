@@ -0,0 +1,172 @@
+use axum::{Extension, Json};
+use chrono::Utc;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::EmailChangeError, response::ApiError, CommonError},
+    extractors::AuthSession,
+    services::{
+        database::{email_verification::PURPOSE_EMAIL_CHANGE, is_unique_violation, DatabaseLayer},
+        email::EmailLayer,
+    },
+    utils::{
+        crypto::verify_string_hash,
+        validation::{validate_numeric_code_format, validate_numeric_code_length},
+    },
+};
+
+// After this many invalid codes the change is locked and has to be requested again
+const MAX_CODE_ATTEMPTS: i64 = 5;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(custom(function = "validate_numeric_code_length"))]
+    #[validate(custom(function = "validate_numeric_code_format"))]
+    code: String,
+    email_verification_id_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    email: String,
+}
+
+#[axum::debug_handler]
+pub async fn email_change_confirm(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<EmailChangeError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        code: payload.code.clone(),
+        email_verification_id_hash: payload.email_verification_id_hash.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Retrieve the pending email change
+
+    let email_verification = match database_layer
+        .query()
+        .email_verification
+        .get(user.id.clone(), PURPOSE_EMAIL_CHANGE)
+        .await
+    {
+        Ok(email_verification) => email_verification,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(EmailChangeError::NoPendingChange));
+        }
+        Err(err) => {
+            return Err(ApiError(EmailChangeError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+
+    let new_email = match email_verification.new_email.clone() {
+        Some(new_email) => new_email,
+        None => return Err(ApiError(EmailChangeError::NoPendingChange)),
+    };
+    println!("2. Pending email change retrieved successfully!");
+
+    // 3. Validate the token, the code and their expiration
+
+    let email_verification_id_matches = verify_string_hash(
+        email_verification.id.id.to_string(),
+        payload.email_verification_id_hash.clone(),
+    );
+
+    if !email_verification_id_matches {
+        return Err(ApiError(EmailChangeError::InvalidToken));
+    }
+
+    if *email_verification.expires_at < Utc::now() {
+        return Err(ApiError(EmailChangeError::ExpiredCode));
+    }
+
+    let attempt_allowed = database_layer
+        .query()
+        .email_verification
+        .consume_attempt(email_verification.id.clone(), MAX_CODE_ATTEMPTS)
+        .await?;
+
+    if !attempt_allowed {
+        return Err(ApiError(EmailChangeError::TooManyAttempts));
+    }
+
+    let code_matches = verify_string_hash(payload.code.clone(), email_verification.code.clone());
+
+    if !code_matches {
+        return Err(ApiError(EmailChangeError::InvalidCode));
+    }
+    println!("3. Email change code validated successfully!");
+
+    // 4. Remove the email change, a concurrent request could have consumed or cancelled it
+
+    match database_layer
+        .query()
+        .email_verification
+        .remove(email_verification.id.clone())
+        .await
+    {
+        Ok(()) => (),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidRequest(_))) => {
+            return Err(ApiError(EmailChangeError::NoPendingChange));
+        }
+        Err(err) => {
+            return Err(ApiError(EmailChangeError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+    println!("4. Email change verification removed successfully!");
+
+    // 5. Update the user email, the address could have been taken since the change was requested
+
+    let user_exists = database_layer
+        .query()
+        .user
+        .check_if_exists(new_email.clone())
+        .await?;
+
+    if user_exists {
+        return Err(ApiError(EmailChangeError::EmailAlreadyExists));
+    }
+
+    let previous_email = user.email.clone();
+
+    let user = match database_layer
+        .query()
+        .user
+        .update_email(user.id, new_email)
+        .await
+    {
+        Ok(user) => user,
+        Err(err) if is_unique_violation(&err, "user_email") => {
+            return Err(ApiError(EmailChangeError::EmailAlreadyExists))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    println!("5. User email updated successfully!");
+
+    // 6. Let the previous address know the change went through
+
+    email_layer
+        .send_email_changed(previous_email, user.email.clone())
+        .await?;
+    println!("6. Email change notification email sent successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Email changed successfully"),
+            email: user.email,
+        }),
+    ))
+}
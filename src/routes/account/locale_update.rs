@@ -0,0 +1,60 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::LocaleError, response::ApiError},
+    extractors::AuthSession,
+    services::{database::DatabaseLayer, i18n::Locale},
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    locale: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    locale: String,
+}
+
+// The stored locale takes precedence over `Accept-Language` for responses and emails
+#[axum::debug_handler]
+pub async fn locale_update(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<LocaleError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        locale: payload.locale.clone(),
+    };
+
+    payload_instance.validate()?;
+
+    let locale = match Locale::from_code(&payload.locale) {
+        Some(locale) => locale,
+        None => return Err(ApiError(LocaleError::UnsupportedLocale)),
+    };
+    println!("1. Validation passed successfully!");
+
+    // 2. Store the locale
+
+    database_layer
+        .query()
+        .user
+        .update_locale(user.id, locale.code().to_string())
+        .await?;
+    println!("2. Locale updated successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Locale updated successfully"),
+            locale: locale.code().to_string(),
+        }),
+    ))
+}
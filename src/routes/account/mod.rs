@@ -0,0 +1,39 @@
+pub mod account_deletion;
+pub mod account_deletion_cancel;
+pub mod data_export;
+pub mod data_export_download;
+pub mod email_change;
+pub mod email_change_cancel;
+pub mod email_change_confirm;
+pub mod locale_update;
+pub mod password_change;
+
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+
+pub use account_deletion::account_deletion;
+pub use account_deletion_cancel::account_deletion_cancel;
+pub use data_export::data_export;
+pub use data_export_download::data_export_download;
+pub use email_change::email_change;
+pub use email_change_cancel::email_change_cancel;
+pub use email_change_confirm::email_change_confirm;
+pub use locale_update::locale_update;
+pub use password_change::password_change;
+
+use crate::setup::AppState;
+
+pub fn account_router() -> Router<AppState> {
+    Router::new()
+        .route("/", delete(account_deletion))
+        .route("/deletion/cancel", post(account_deletion_cancel))
+        .route("/export", post(data_export))
+        .route("/export/:token", get(data_export_download))
+        .route("/password", post(password_change))
+        .route("/email", post(email_change))
+        .route("/email/confirm", post(email_change_confirm))
+        .route("/email/cancel", post(email_change_cancel))
+        .route("/locale", put(locale_update))
+}
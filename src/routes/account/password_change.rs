@@ -0,0 +1,130 @@
+use axum::{extract::State, Extension, Json};
+use chrono::Utc;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{account::PasswordChangeError, response::ApiError},
+    extractors::{AuthSession, ClientInfo},
+    routes::auth::signin::register_failed_attempt,
+    services::{
+        breached_password::BreachedPasswordMode, database::DatabaseLayer, email::EmailLayer,
+    },
+    setup::AppState,
+    utils::crypto::{hash_password, verify_password_hash},
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    current_password: String,
+    new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    password_breached: bool,
+}
+
+#[axum::debug_handler]
+pub async fn password_change(
+    State(app_state): State<AppState>,
+    AuthSession { session, user }: AuthSession,
+    client_info: ClientInfo,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<PasswordChangeError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        current_password: payload.current_password.clone(),
+        new_password: payload.new_password.clone(),
+    };
+
+    payload_instance.validate()?;
+
+    app_state
+        .config
+        .password_policy
+        .check(&payload.new_password, &user.email)
+        .map_err(|errors| ApiError(PasswordChangeError::WeakPassword(errors)))?;
+
+    let password_breached = app_state
+        .breached_passwords
+        .is_breached(&payload.new_password)
+        .await;
+
+    if password_breached && app_state.breached_passwords.mode == BreachedPasswordMode::Reject {
+        return Err(ApiError(PasswordChangeError::BreachedPassword));
+    }
+    println!("1. Validation passed successfully!");
+
+    // 2. Verify the current password, failures count towards locking the account like failed
+    // signins do, so a hijacked session can't be used to guess the password
+
+    if user
+        .locked_until
+        .as_ref()
+        .is_some_and(|locked_until| **locked_until > Utc::now())
+    {
+        return Err(ApiError(PasswordChangeError::AccountLocked));
+    }
+
+    let password_matches =
+        verify_password_hash(payload.current_password.clone(), user.password_hash.clone()).await?;
+
+    if !password_matches {
+        let account_locked = register_failed_attempt(
+            &database_layer,
+            &email_layer,
+            &app_state.config.signin_lockout,
+            &user,
+            client_info.ip_address,
+        )
+        .await?;
+
+        if account_locked {
+            return Err(ApiError(PasswordChangeError::AccountLocked));
+        }
+
+        return Err(ApiError(PasswordChangeError::InvalidCurrentPassword));
+    }
+    println!("2. Current password confirmed successfully!");
+
+    // 3. Update user password
+
+    let password_hash = hash_password(payload.new_password.clone()).await?;
+
+    let user = database_layer
+        .query()
+        .user
+        .update_password(user.id, password_hash)
+        .await?;
+    println!("3. User password updated successfully!");
+
+    // 4. Remove all other user sessions, the current one stays signed in
+
+    database_layer
+        .query()
+        .session
+        .invalidate_all_except(user.id.clone(), session.id)
+        .await?;
+    println!("4. Other user sessions invalidated successfully!");
+
+    // 5. Send email to user notifying them about the password change
+
+    email_layer
+        .send_password_changed(user.email.clone())
+        .await?;
+    println!("5. Password change notification email sent successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Password changed successfully"),
+            password_breached,
+        }),
+    ))
+}
@@ -0,0 +1,86 @@
+use axum::{Extension, Json};
+use chrono::Utc;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+use validator::Validate;
+
+use crate::{
+    errors::{auth::AccountUnlockError, response::ApiError, CommonError},
+    services::database::DatabaseLayer,
+    utils::crypto::hash_token,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn account_unlock(
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<AccountUnlockError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        token: payload.token.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Consume the unlock token, it's single-use even if it turns out to be expired
+
+    let account_unlock_id =
+        Thing::from((String::from("account_unlock"), hash_token(&payload.token)));
+
+    let account_unlock = match database_layer
+        .query()
+        .account_unlock
+        .consume(account_unlock_id)
+        .await
+    {
+        Ok(account_unlock) => account_unlock,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(AccountUnlockError::InvalidToken));
+        }
+        Err(err) => {
+            return Err(ApiError(AccountUnlockError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+
+    if *account_unlock.expires_at < Utc::now() {
+        return Err(ApiError(AccountUnlockError::TokenExpired));
+    }
+    println!("2. Unlock token consumed successfully!");
+
+    // 3. Unlock the account and forget the failed attempts that led to the lock
+
+    database_layer
+        .query()
+        .user
+        .unlock(account_unlock.user.clone())
+        .await?;
+
+    database_layer
+        .query()
+        .failed_signin
+        .clear_for_user(account_unlock.user)
+        .await?;
+    println!("3. Account unlocked successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Account unlocked successfully"),
+        }),
+    ))
+}
@@ -1,26 +1,34 @@
-use axum::{Extension, Json};
-use hyper::StatusCode;
+use axum::{
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use hyper::{header::SET_COOKIE, StatusCode};
 use serde::{Deserialize, Serialize};
-use surrealdb::sql::Thing;
 use validator::Validate;
 
 use crate::{
-    errors::{auth::EmailVerificationError, response::ApiError},
-    services::{database::DatabaseLayer, email::EmailLayer},
+    errors::{auth::EmailVerificationError, response::ApiError, CommonError},
+    extractors::AnySession,
+    services::{
+        database::{email_verification::PURPOSE_SIGNUP, DatabaseLayer},
+        email::EmailLayer,
+    },
     utils::{
+        cookies::remove_session_cookie,
         crypto::verify_string_hash,
-        validation::{
-            validate_email_verification_code_format, validate_email_verification_code_length,
-        },
+        validation::{validate_numeric_code_format, validate_numeric_code_length},
     },
 };
 
+// After this many invalid codes the verification is locked and a new code has to be requested
+const MAX_CODE_ATTEMPTS: i64 = 5;
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct RoutePayload {
-    #[validate(custom(function = "validate_email_verification_code_length"))]
-    #[validate(custom(function = "validate_email_verification_code_format"))]
+    #[validate(custom(function = "validate_numeric_code_length"))]
+    #[validate(custom(function = "validate_numeric_code_format"))]
     code: String,
-    user_id: String,
     email_verification_id_hash: String,
 }
 
@@ -29,16 +37,15 @@ pub struct RouteOutput {
     message: String,
 }
 
-// TODO: Make the route work only if the session with user id was provided
 pub async fn email_verification(
+    AnySession { session, user }: AnySession,
     Extension(database_layer): Extension<DatabaseLayer>,
     Extension(email_layer): Extension<EmailLayer>,
     Json(payload): Json<RoutePayload>,
-) -> Result<(StatusCode, Json<RouteOutput>), ApiError<EmailVerificationError>> {
+) -> Result<(StatusCode, Response), ApiError<EmailVerificationError>> {
     // 1. Validate payload input
     let payload_instance = RoutePayload {
         code: payload.code.clone(),
-        user_id: payload.user_id.clone(),
         email_verification_id_hash: payload.email_verification_id_hash.clone(),
     };
 
@@ -47,16 +54,31 @@ pub async fn email_verification(
 
     // 2. Check if email verification exists for a user
 
-    let user_id = Thing::from((String::from("user"), payload.user_id.clone()));
+    if user.email_verified {
+        return Err(ApiError(EmailVerificationError::EmailAlreadyVerified));
+    }
+
+    let user_id = user.id.clone();
 
-    let email_verification_response = database_layer
+    let email_verification_response = match database_layer
         .query()
         .email_verification
-        .get(user_id.clone())
-        .await?;
+        .get(user_id.clone(), PURPOSE_SIGNUP)
+        .await
+    {
+        Ok(email_verification) => email_verification,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(EmailVerificationError::CodeAlreadyUsed));
+        }
+        Err(err) => {
+            return Err(ApiError(EmailVerificationError::Common(
+                CommonError::Database(err),
+            )))
+        }
+    };
     println!("2. Email verification existence checked successfully!");
 
-    // 3. Validate unauthorized session
+    // 3. Validate the token, the code and their expiration
 
     let email_verification_id_matches = verify_string_hash(
         email_verification_response.id.id.to_string(),
@@ -67,45 +89,91 @@ pub async fn email_verification(
         return Err(ApiError(EmailVerificationError::InvalidToken));
     }
 
-    let email_verification_id = Thing::from((
-        String::from("email_verification"),
-        email_verification_response.id.id.to_string(),
-    ));
+    if *email_verification_response.expires_at < Utc::now() {
+        return Err(ApiError(EmailVerificationError::ExpiredCode));
+    }
 
-    // 3. Update user verified status
-    let user = database_layer
+    let attempt_allowed = database_layer
         .query()
-        .user
-        .verify_user(user_id.clone())
+        .email_verification
+        .consume_attempt(email_verification_response.id.clone(), MAX_CODE_ATTEMPTS)
         .await?;
-    println!("2. User verified status updated successfully!");
 
-    // 4. Remove email verification
-    database_layer
+    if !attempt_allowed {
+        return Err(ApiError(EmailVerificationError::TooManyAttempts));
+    }
+
+    let code_matches = verify_string_hash(
+        payload.code.clone(),
+        email_verification_response.code.clone(),
+    );
+
+    if !code_matches {
+        return Err(ApiError(EmailVerificationError::InvalidCode));
+    }
+    println!("3. Email verification code validated successfully!");
+
+    // 4. Remove email verification, a concurrent request could have consumed it already
+
+    match database_layer
         .query()
         .email_verification
-        .remove(email_verification_id.clone())
+        .remove(email_verification_response.id.clone())
+        .await
+    {
+        Ok(()) => (),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidRequest(_))) => {
+            return Err(ApiError(EmailVerificationError::CodeAlreadyUsed));
+        }
+        Err(err) => {
+            return Err(ApiError(EmailVerificationError::Common(
+                CommonError::Database(err),
+            )))
+        }
+    };
+    println!("4. Email verification removed successfully!");
+
+    // 5. Update user verified status
+
+    let user = database_layer
+        .query()
+        .user
+        .verify_user(user_id.clone())
         .await?;
-    println!("3. Email verification removed successfully!");
+    println!("5. User verified status updated successfully!");
 
-    // 5. Remove all user sessions (should only have unauthrized ones)
+    // 6. Remove the unauthorized user sessions, sessions authorized during a grace period stay
+    // signed in. A caller with an unauthorized session has to sign in again, so its session
+    // cookie is expired as well
 
     database_layer
         .query()
         .session
-        .invalidate_all(user_id)
+        .invalidate_unauthorized(user_id)
         .await?;
 
-    // 6. Send email to user confirming the account verification
+    let cookie = (!session.authorized).then(remove_session_cookie);
+    println!("6. Unauthorized user sessions removed successfully!");
+
+    // 7. Send email to user confirming the account verification
 
     email_layer
         .send_email_verification_confirmation(user.first().unwrap().email.clone())
         .await?;
 
-    Ok((
+    let mut response = (
         StatusCode::OK,
         Json(RouteOutput {
             message: String::from("Email verified successfully"),
         }),
-    ))
+    )
+        .into_response();
+
+    if let Some(cookie) = cookie {
+        response
+            .headers_mut()
+            .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+    }
+
+    Ok((StatusCode::OK, response))
 }
@@ -0,0 +1,95 @@
+use axum::{Extension, Json};
+use chrono::Duration;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{auth::EmailVerificationError, response::ApiError},
+    extractors::AnySession,
+    services::{
+        database::{email_verification::DispatchReservation, DatabaseLayer},
+        email::EmailLayer,
+    },
+    utils::{crypto::hash_string, random::generate_random_code},
+};
+
+const RESEND_COOLDOWN_SECONDS: i64 = 60;
+const RESEND_DAILY_LIMIT: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn email_verification_resend(
+    AnySession { user, .. }: AnySession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<EmailVerificationError>> {
+    // 1. Check if the email still needs to be verified
+
+    if user.email_verified {
+        return Err(ApiError(EmailVerificationError::EmailAlreadyVerified));
+    }
+
+    // 2. Enforce the cooldown and the daily limit, the dispatch is recorded along with the check
+
+    let reservation = database_layer
+        .query()
+        .email_verification
+        .reserve_dispatch(
+            user.id.clone(),
+            Duration::seconds(RESEND_COOLDOWN_SECONDS),
+            RESEND_DAILY_LIMIT,
+        )
+        .await?;
+
+    let dispatch_id = match reservation {
+        DispatchReservation::Reserved(dispatch_id) => dispatch_id,
+        DispatchReservation::Cooldown => {
+            return Err(ApiError(EmailVerificationError::ResendCooldown))
+        }
+        DispatchReservation::LimitReached => {
+            return Err(ApiError(EmailVerificationError::ResendLimitReached))
+        }
+    };
+    println!("2. Resend limits checked successfully!");
+
+    // 3. Create a new email verification, replacing the previous one
+
+    let verification_code = generate_random_code(6);
+    let verification_code_hash = hash_string(verification_code.clone());
+
+    let email_verification = database_layer
+        .query()
+        .email_verification
+        .create(verification_code_hash, user.id.clone())
+        .await?;
+    println!("3. Email verification created successfully!");
+
+    // 4. Send email verification email
+
+    let token_hash = hash_string(email_verification.id.id.clone().to_string());
+
+    // Failed sends don't count towards the cooldown, so the user can try again right away
+    let send_result = email_layer
+        .send_email_verification(user.email, verification_code, token_hash)
+        .await;
+
+    database_layer
+        .query()
+        .email_verification
+        .settle_dispatch(dispatch_id, user.id, send_result.is_ok())
+        .await?;
+
+    send_result?;
+    println!("4. Email verification email sent successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Verification email sent successfully"),
+        }),
+    ))
+}
@@ -0,0 +1,100 @@
+use axum::{Extension, Json};
+use chrono::{Duration, Utc};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{auth::MagicLinkError, response::ApiError, CommonError},
+    services::{database::DatabaseLayer, email::EmailLayer, i18n::with_locale},
+    utils::{
+        crypto::{generate_token, hash_string},
+        random::generate_random_code,
+    },
+};
+
+const REQUEST_COOLDOWN_SECONDS: i64 = 60;
+const REQUEST_DAILY_LIMIT: usize = 5;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(email)]
+    email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn magic_link(
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<MagicLinkError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        email: payload.email.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    let output = Json(RouteOutput {
+        message: String::from("If an account exists for this email, a signin link was sent"),
+    });
+
+    // 2. Retrieve user, unknown emails get the same response to avoid leaking accounts
+
+    let user = match database_layer.query().user.get(payload.email.clone()).await {
+        Ok(user) => user,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Ok((StatusCode::OK, output));
+        }
+        Err(err) => return Err(ApiError(MagicLinkError::Common(CommonError::Database(err)))),
+    };
+
+    // 3. Enforce the cooldown and the daily limit, requests over the limits get the same
+    // response so they don't reveal whether an account exists
+
+    let now = Utc::now();
+
+    let dispatches = database_layer
+        .query()
+        .magic_link
+        .dispatches_since(user.id.clone(), now - Duration::days(1))
+        .await?;
+
+    let cooldown_active = dispatches.first().is_some_and(|last_dispatch| {
+        **last_dispatch > now - Duration::seconds(REQUEST_COOLDOWN_SECONDS)
+    });
+
+    if dispatches.len() >= REQUEST_DAILY_LIMIT || cooldown_active {
+        return Ok((StatusCode::OK, output));
+    }
+    println!("3. Request limits checked successfully!");
+
+    // 4. Create magic link in the database
+
+    let token = generate_token();
+    let code = generate_random_code(6);
+
+    database_layer
+        .query()
+        .magic_link
+        .create(&token, hash_string(code.clone()), user.id.clone())
+        .await?;
+    println!("4. Magic link created successfully!");
+
+    // 5. Send the link and the code
+
+    with_locale(
+        user.preferred_locale(),
+        email_layer.send_magic_link(user.email.clone(), code, token),
+    )
+    .await?;
+    println!("5. Magic link email sent successfully!");
+
+    Ok((StatusCode::OK, output))
+}
@@ -0,0 +1,219 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use hyper::{header::SET_COOKIE, StatusCode};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+use validator::Validate;
+
+use crate::{
+    errors::{auth::MagicLinkError, response::ApiError, CommonError},
+    extractors::ClientInfo,
+    services::{database::DatabaseLayer, email::EmailLayer},
+    setup::{AppState, VerificationAccess},
+    utils::{
+        cookies::set_session_cookie,
+        crypto::{hash_token, verify_string_hash},
+        validation::{validate_numeric_code_format, validate_numeric_code_length},
+    },
+};
+
+use super::signin::{register_failed_attempt, two_factor_methods};
+
+const MAX_CODE_ATTEMPTS: i64 = 5;
+
+// Either the token from the link, or the email together with the 6-digit code
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    token: Option<String>,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(custom(function = "validate_numeric_code_length"))]
+    #[validate(custom(function = "validate_numeric_code_format"))]
+    code: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    two_factor_required: bool,
+    two_factor_methods: Vec<String>,
+    email_verification_required: bool,
+}
+
+#[axum::debug_handler]
+pub async fn magic_link_consume(
+    State(app_state): State<AppState>,
+    client_info: ClientInfo,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Response), ApiError<MagicLinkError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        token: payload.token.clone(),
+        email: payload.email.clone(),
+        code: payload.code.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Resolve the magic link either by the token or by the email and code
+
+    let magic_link_id = match (payload.token, payload.email, payload.code) {
+        (Some(token), _, _) => Thing::from((String::from("magic_link"), hash_token(&token))),
+        (None, Some(email), Some(code)) => {
+            let user = match database_layer.query().user.get(email).await {
+                Ok(user) => user,
+                Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+                    return Err(ApiError(MagicLinkError::InvalidCode));
+                }
+                Err(err) => {
+                    return Err(ApiError(MagicLinkError::Common(CommonError::Database(err))))
+                }
+            };
+
+            let magic_link = match database_layer.query().magic_link.get(user.id.clone()).await {
+                Ok(magic_link) => magic_link,
+                Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+                    return Err(ApiError(MagicLinkError::InvalidCode));
+                }
+                Err(err) => {
+                    return Err(ApiError(MagicLinkError::Common(CommonError::Database(err))))
+                }
+            };
+
+            if let Some(locked_until) = &user.locked_until {
+                if **locked_until > Utc::now() {
+                    return Err(ApiError(MagicLinkError::AccountLocked));
+                }
+            }
+
+            // The code is short, so it only survives a limited amount of guesses
+            let attempt_allowed = database_layer
+                .query()
+                .magic_link
+                .consume_attempt(magic_link.id.clone(), MAX_CODE_ATTEMPTS)
+                .await?;
+
+            if !attempt_allowed {
+                // Concurrent guesses may have removed the link already
+                if let Err(err) = database_layer
+                    .query()
+                    .magic_link
+                    .consume(magic_link.id)
+                    .await
+                {
+                    println!("Failed to remove the magic link: {}", err);
+                }
+                return Err(ApiError(MagicLinkError::TooManyAttempts));
+            }
+
+            // Wrong codes count towards locking the account like wrong passwords, which limits
+            // the guesses across newly requested links as well
+            if !verify_string_hash(code, magic_link.code.clone()) {
+                let account_locked = register_failed_attempt(
+                    &database_layer,
+                    &email_layer,
+                    &app_state.config.signin_lockout,
+                    &user,
+                    client_info.ip_address.clone(),
+                )
+                .await?;
+
+                if account_locked {
+                    return Err(ApiError(MagicLinkError::AccountLocked));
+                }
+
+                return Err(ApiError(MagicLinkError::InvalidCode));
+            }
+
+            magic_link.id
+        }
+        _ => return Err(ApiError(MagicLinkError::MissingCredentials)),
+    };
+
+    // 3. Consume the magic link, deleting it guarantees it can only be used once
+
+    let magic_link = match database_layer
+        .query()
+        .magic_link
+        .consume(magic_link_id)
+        .await
+    {
+        Ok(magic_link) => magic_link,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(MagicLinkError::InvalidToken));
+        }
+        Err(err) => return Err(ApiError(MagicLinkError::Common(CommonError::Database(err)))),
+    };
+
+    if *magic_link.expires_at < Utc::now() {
+        return Err(ApiError(MagicLinkError::TokenExpired));
+    }
+    println!("3. Magic link consumed successfully!");
+
+    // 4. Create a session, the magic link replaces the password so 2FA still applies
+
+    let user = database_layer
+        .query()
+        .user
+        .get_by_id(magic_link.user)
+        .await?;
+
+    if user.deletion_scheduled_at.is_some() {
+        return Err(ApiError(MagicLinkError::AccountPendingDeletion));
+    }
+
+    let verification_access = app_state.config.email_verification_policy.access(&user);
+
+    if verification_access == VerificationAccess::Denied {
+        return Err(ApiError(MagicLinkError::AccountNotVerified));
+    }
+
+    let email_verification_required = verification_access == VerificationAccess::Restricted;
+
+    let two_factor_methods = two_factor_methods(&database_layer, &user).await?;
+    let two_factor_required = !two_factor_methods.is_empty();
+    let authorized = !two_factor_required && !email_verification_required;
+
+    let (_session, session_token) = database_layer
+        .query()
+        .session
+        .create(user.id, authorized, client_info.session_metadata())
+        .await?;
+    println!("4. Session created successfully!");
+
+    // 5. Create a session cookie
+
+    let cookie = set_session_cookie(session_token, authorized);
+
+    let message = if two_factor_required {
+        String::from("Two-factor authentication required")
+    } else if email_verification_required {
+        String::from("Email verification required")
+    } else {
+        String::from("Signin completed successfully!")
+    };
+
+    let mut response = (
+        StatusCode::OK,
+        Json(RouteOutput {
+            message,
+            two_factor_required,
+            two_factor_methods,
+            email_verification_required,
+        }),
+    )
+        .into_response();
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+
+    Ok((StatusCode::OK, response))
+}
@@ -1,16 +1,53 @@
+pub mod account_unlock;
 pub mod email_verification;
+pub mod email_verification_resend;
+pub mod magic_link;
+pub mod magic_link_consume;
+pub mod passkey_registration;
+pub mod passkey_registration_options;
+pub mod passkey_signin;
+pub mod passkey_signin_options;
 pub mod password_reset;
 pub mod password_reset_request;
+pub mod session_revoke;
+pub mod sessions;
 pub mod signin;
+pub mod signin_recovery;
+pub mod signin_two_factor;
+pub mod signout;
+pub mod signout_all;
 pub mod signup;
+pub mod two_factor_confirm;
+pub mod two_factor_recovery_codes;
+pub mod two_factor_setup;
 
-use axum::{routing::post, Router};
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
 
+pub use account_unlock::account_unlock;
 pub use email_verification::email_verification;
+pub use email_verification_resend::email_verification_resend;
+pub use magic_link::magic_link;
+pub use magic_link_consume::magic_link_consume;
+pub use passkey_registration::passkey_registration;
+pub use passkey_registration_options::passkey_registration_options;
+pub use passkey_signin::passkey_signin;
+pub use passkey_signin_options::passkey_signin_options;
 pub use password_reset::password_reset;
 pub use password_reset_request::password_reset_request;
+pub use session_revoke::session_revoke;
+pub use sessions::sessions;
 pub use signin::signin;
+pub use signin_recovery::signin_recovery;
+pub use signin_two_factor::signin_two_factor;
+pub use signout::signout;
+pub use signout_all::signout_all;
 pub use signup::signup;
+pub use two_factor_confirm::two_factor_confirm;
+pub use two_factor_recovery_codes::two_factor_recovery_codes;
+pub use two_factor_setup::two_factor_setup;
 
 use crate::setup::AppState;
 
@@ -18,7 +55,30 @@ pub fn auth_router() -> Router<AppState> {
     Router::new()
         .route("/signup", post(signup))
         .route("/signin", post(signin))
+        .route("/signin/2fa", post(signin_two_factor))
+        .route("/signin/recovery", post(signin_recovery))
+        .route("/signout", post(signout))
+        .route("/signout-all", post(signout_all))
+        .route("/magic-link", post(magic_link))
+        .route("/magic-link/consume", post(magic_link_consume))
+        .route("/sessions", get(sessions))
+        .route("/sessions/:id", delete(session_revoke))
+        .route("/2fa/totp/setup", post(two_factor_setup))
+        .route("/2fa/totp/confirm", post(two_factor_confirm))
+        .route("/2fa/recovery-codes", post(two_factor_recovery_codes))
+        .route(
+            "/passkeys/register/options",
+            post(passkey_registration_options),
+        )
+        .route("/passkeys/register", post(passkey_registration))
+        .route("/passkeys/signin/options", post(passkey_signin_options))
+        .route("/passkeys/signin", post(passkey_signin))
         .route("/email-verification", post(email_verification))
+        .route(
+            "/email-verification/resend",
+            post(email_verification_resend),
+        )
         .route("/password_reset", post(password_reset))
         .route("/password-reset-request", post(password_reset_request))
+        .route("/unlock", post(account_unlock))
 }
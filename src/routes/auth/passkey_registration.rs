@@ -0,0 +1,158 @@
+use axum::{extract::State, Extension, Json};
+use chrono::Utc;
+use data_encoding::BASE64URL_NOPAD;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+use validator::Validate;
+
+use crate::{
+    errors::{auth::PasskeyError, response::ApiError, CommonError},
+    extractors::AuthSession,
+    services::database::{webauthn_challenge::PURPOSE_REGISTRATION, DatabaseLayer},
+    setup::AppState,
+    utils::webauthn::{decode_base64url, verify_registration, CeremonyExpectation},
+};
+
+use super::{signin::two_factor_methods, two_factor_confirm::issue_recovery_codes};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    challenge_id: String,
+    credential_id: String,
+    client_data_json: String,
+    attestation_object: String,
+    #[validate(length(max = 64))]
+    name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    // Only issued when the passkey is the first second factor of the user
+    recovery_codes: Option<Vec<String>>,
+}
+
+#[axum::debug_handler]
+pub async fn passkey_registration(
+    State(app_state): State<AppState>,
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<PasskeyError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        challenge_id: payload.challenge_id.clone(),
+        credential_id: payload.credential_id.clone(),
+        client_data_json: payload.client_data_json.clone(),
+        attestation_object: payload.attestation_object.clone(),
+        name: payload.name.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Consume the registration challenge
+
+    let webauthn_challenge_id = Thing::from((
+        String::from("webauthn_challenge"),
+        payload.challenge_id.clone(),
+    ));
+
+    let webauthn_challenge = match database_layer
+        .query()
+        .webauthn_challenge
+        .consume(webauthn_challenge_id)
+        .await
+    {
+        Ok(webauthn_challenge) => webauthn_challenge,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(PasskeyError::InvalidChallenge));
+        }
+        Err(err) => return Err(ApiError(PasskeyError::Common(CommonError::Database(err)))),
+    };
+
+    if webauthn_challenge.purpose != PURPOSE_REGISTRATION
+        || webauthn_challenge.user.as_ref() != Some(&user.id)
+    {
+        return Err(ApiError(PasskeyError::InvalidChallenge));
+    }
+
+    if *webauthn_challenge.expires_at < Utc::now() {
+        return Err(ApiError(PasskeyError::ChallengeExpired));
+    }
+    println!("2. WebAuthn challenge consumed successfully!");
+
+    // 3. Verify client data and authenticator data
+
+    let client_data_json =
+        decode_base64url(&payload.client_data_json).ok_or(PasskeyError::VerificationFailed)?;
+    let attestation_object =
+        decode_base64url(&payload.attestation_object).ok_or(PasskeyError::VerificationFailed)?;
+    let credential_id =
+        decode_base64url(&payload.credential_id).ok_or(PasskeyError::VerificationFailed)?;
+
+    let registration = verify_registration(
+        &client_data_json,
+        &attestation_object,
+        &credential_id,
+        &CeremonyExpectation {
+            challenge: &webauthn_challenge.challenge,
+            origin: &app_state.config.webauthn_origin,
+            rp_id: &app_state.config.webauthn_rp_id,
+        },
+    )
+    .ok_or(PasskeyError::VerificationFailed)?;
+
+    let public_key = registration
+        .public_key
+        .ok_or(PasskeyError::UnsupportedCredential)?;
+    println!("3. Passkey attestation verified successfully!");
+
+    // 4. Store the passkey
+
+    let first_second_factor = two_factor_methods(&database_layer, &user).await?.is_empty();
+
+    let credential_id = BASE64URL_NOPAD.encode(&credential_id);
+
+    match database_layer
+        .query()
+        .passkey
+        .get_by_credential_id(credential_id.clone())
+        .await
+    {
+        Ok(_) => return Err(ApiError(PasskeyError::CredentialAlreadyRegistered)),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => (),
+        Err(err) => return Err(ApiError(PasskeyError::Common(CommonError::Database(err)))),
+    };
+
+    database_layer
+        .query()
+        .passkey
+        .create(
+            user.id.clone(),
+            credential_id,
+            hex::encode(public_key),
+            registration.sign_count as i64,
+            payload.name,
+        )
+        .await?;
+    println!("4. Passkey stored successfully!");
+
+    // 5. Passkeys count as a second factor, so the first one comes with recovery codes
+
+    let recovery_codes = if first_second_factor {
+        Some(issue_recovery_codes(&database_layer, user.id).await?)
+    } else {
+        None
+    };
+    println!("5. Recovery codes checked successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Passkey registered successfully"),
+            recovery_codes,
+        }),
+    ))
+}
@@ -0,0 +1,76 @@
+use axum::{extract::State, Extension, Json};
+use data_encoding::BASE64URL_NOPAD;
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    errors::{auth::PasskeyError, response::ApiError},
+    extractors::AuthSession,
+    services::database::{webauthn_challenge::PURPOSE_REGISTRATION, DatabaseLayer},
+    setup::AppState,
+    utils::webauthn::COSE_ALGORITHM_ES256,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    challenge_id: String,
+    // PublicKeyCredentialCreationOptions, passed to `navigator.credentials.create()`
+    public_key: Value,
+}
+
+#[axum::debug_handler]
+pub async fn passkey_registration_options(
+    State(app_state): State<AppState>,
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<PasskeyError>> {
+    // 1. Create a registration challenge bound to the user
+
+    let webauthn_challenge = database_layer
+        .query()
+        .webauthn_challenge
+        .create(PURPOSE_REGISTRATION, Some(user.id.clone()))
+        .await?;
+    println!("1. WebAuthn challenge created successfully!");
+
+    // 2. Exclude already registered passkeys so the same authenticator isn't registered twice
+
+    let exclude_credentials: Vec<Value> = database_layer
+        .query()
+        .passkey
+        .list(user.id.clone())
+        .await?
+        .into_iter()
+        .map(|passkey| json!({ "type": "public-key", "id": passkey.credential_id }))
+        .collect();
+
+    let public_key = json!({
+        "challenge": webauthn_challenge.challenge,
+        "rp": {
+            "id": app_state.config.webauthn_rp_id,
+            "name": app_state.config.webauthn_rp_name,
+        },
+        "user": {
+            "id": BASE64URL_NOPAD.encode(user.id.id.to_raw().as_bytes()),
+            "name": user.email,
+            "displayName": user.email,
+        },
+        "pubKeyCredParams": [{ "type": "public-key", "alg": COSE_ALGORITHM_ES256 }],
+        "timeout": 300000,
+        "attestation": "none",
+        "excludeCredentials": exclude_credentials,
+        "authenticatorSelection": {
+            "residentKey": "preferred",
+            "userVerification": "preferred",
+        },
+    });
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            challenge_id: webauthn_challenge.id.id.to_raw(),
+            public_key,
+        }),
+    ))
+}
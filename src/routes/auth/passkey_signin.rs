@@ -0,0 +1,191 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use hyper::{header::SET_COOKIE, StatusCode};
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+use validator::Validate;
+
+use crate::{
+    errors::{auth::PasskeyError, response::ApiError, CommonError},
+    extractors::{AnySession, ClientInfo},
+    services::database::{webauthn_challenge::PURPOSE_AUTHENTICATION, DatabaseLayer},
+    setup::{AppState, VerificationAccess},
+    utils::{
+        cookies::set_session_cookie,
+        webauthn::{decode_base64url, verify_assertion, CeremonyExpectation},
+    },
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    challenge_id: String,
+    credential_id: String,
+    client_data_json: String,
+    authenticator_data: String,
+    signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn passkey_signin(
+    State(app_state): State<AppState>,
+    client_info: ClientInfo,
+    session: Option<AnySession>,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Response), ApiError<PasskeyError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        challenge_id: payload.challenge_id.clone(),
+        credential_id: payload.credential_id.clone(),
+        client_data_json: payload.client_data_json.clone(),
+        authenticator_data: payload.authenticator_data.clone(),
+        signature: payload.signature.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Consume the authentication challenge
+
+    let webauthn_challenge_id = Thing::from((
+        String::from("webauthn_challenge"),
+        payload.challenge_id.clone(),
+    ));
+
+    let webauthn_challenge = match database_layer
+        .query()
+        .webauthn_challenge
+        .consume(webauthn_challenge_id)
+        .await
+    {
+        Ok(webauthn_challenge) => webauthn_challenge,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(PasskeyError::InvalidChallenge));
+        }
+        Err(err) => return Err(ApiError(PasskeyError::Common(CommonError::Database(err)))),
+    };
+
+    if webauthn_challenge.purpose != PURPOSE_AUTHENTICATION {
+        return Err(ApiError(PasskeyError::InvalidChallenge));
+    }
+
+    if *webauthn_challenge.expires_at < Utc::now() {
+        return Err(ApiError(PasskeyError::ChallengeExpired));
+    }
+    println!("2. WebAuthn challenge consumed successfully!");
+
+    // 3. Retrieve the passkey
+
+    let passkey = match database_layer
+        .query()
+        .passkey
+        .get_by_credential_id(payload.credential_id.trim_end_matches('=').to_string())
+        .await
+    {
+        Ok(passkey) => passkey,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(PasskeyError::CredentialNotFound));
+        }
+        Err(err) => return Err(ApiError(PasskeyError::Common(CommonError::Database(err)))),
+    };
+
+    if let Some(challenge_user) = &webauthn_challenge.user {
+        if *challenge_user != passkey.user {
+            return Err(ApiError(PasskeyError::VerificationFailed));
+        }
+    }
+
+    // 4. Verify the assertion, as a second factor the passkey replaces the pending session and as
+    // a primary factor it has to prove user verification on its own
+
+    let pending_session = session
+        .map(|AnySession { session, .. }| session)
+        .filter(|session| !session.authorized && session.user == passkey.user);
+
+    let client_data_json =
+        decode_base64url(&payload.client_data_json).ok_or(PasskeyError::VerificationFailed)?;
+    let authenticator_data_bytes =
+        decode_base64url(&payload.authenticator_data).ok_or(PasskeyError::VerificationFailed)?;
+    let signature = decode_base64url(&payload.signature).ok_or(PasskeyError::VerificationFailed)?;
+    let public_key =
+        hex::decode(&passkey.public_key).map_err(|_| PasskeyError::VerificationFailed)?;
+
+    let sign_count = verify_assertion(
+        &client_data_json,
+        &authenticator_data_bytes,
+        &signature,
+        &public_key,
+        passkey.sign_count as u32,
+        pending_session.is_none(),
+        &CeremonyExpectation {
+            challenge: &webauthn_challenge.challenge,
+            origin: &app_state.config.webauthn_origin,
+            rp_id: &app_state.config.webauthn_rp_id,
+        },
+    )
+    .ok_or(PasskeyError::VerificationFailed)?;
+
+    database_layer
+        .query()
+        .passkey
+        .update_usage(passkey.id, sign_count as i64)
+        .await?;
+    println!("4. Passkey assertion verified successfully!");
+
+    // 5. Replace the pending session
+
+    if let Some(pending_session) = pending_session {
+        database_layer
+            .query()
+            .session
+            .remove(pending_session.id)
+            .await?;
+    }
+    println!("5. Pending session replaced successfully!");
+
+    // 6. Create a session, unverified users stay restricted by the verification policy
+
+    let user = database_layer.query().user.get_by_id(passkey.user).await?;
+
+    if user.deletion_scheduled_at.is_some() {
+        return Err(ApiError(PasskeyError::AccountPendingDeletion));
+    }
+
+    let authorized = match app_state.config.email_verification_policy.access(&user) {
+        VerificationAccess::Full => true,
+        VerificationAccess::Restricted => false,
+        VerificationAccess::Denied => return Err(ApiError(PasskeyError::AccountNotVerified)),
+    };
+
+    let (_session, session_token) = database_layer
+        .query()
+        .session
+        .create(user.id, authorized, client_info.session_metadata())
+        .await?;
+    println!("6. Session created successfully!");
+
+    let cookie = set_session_cookie(session_token, authorized);
+
+    let message = if authorized {
+        String::from("Signin completed successfully!")
+    } else {
+        String::from("Email verification required")
+    };
+
+    let mut response = (StatusCode::OK, Json(RouteOutput { message })).into_response();
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+
+    Ok((StatusCode::OK, response))
+}
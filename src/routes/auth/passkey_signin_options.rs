@@ -0,0 +1,93 @@
+use axum::{extract::State, Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use validator::Validate;
+
+use crate::{
+    errors::{auth::PasskeyError, response::ApiError, CommonError},
+    extractors::AnySession,
+    services::database::{webauthn_challenge::PURPOSE_AUTHENTICATION, DatabaseLayer},
+    setup::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(email)]
+    #[serde(default)]
+    email: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    challenge_id: String,
+    // PublicKeyCredentialRequestOptions, passed to `navigator.credentials.get()`
+    public_key: Value,
+}
+
+// Works both for passwordless signin and as the second factor of a pending `signin` session
+#[axum::debug_handler]
+pub async fn passkey_signin_options(
+    State(app_state): State<AppState>,
+    session: Option<AnySession>,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<PasskeyError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        email: payload.email.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Resolve the user, a pending 2FA session takes precedence over the provided email
+
+    let user_id = match (session, payload.email) {
+        (Some(AnySession { session, user }), _) if !session.authorized => Some(user.id),
+        (_, Some(email)) => match database_layer.query().user.get(email).await {
+            Ok(user) => Some(user.id),
+            // Unknown emails fall back to discoverable credentials to avoid leaking accounts
+            Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => None,
+            Err(err) => return Err(ApiError(PasskeyError::Common(CommonError::Database(err)))),
+        },
+        _ => None,
+    };
+
+    // 3. Create an authentication challenge
+
+    let webauthn_challenge = database_layer
+        .query()
+        .webauthn_challenge
+        .create(PURPOSE_AUTHENTICATION, user_id.clone())
+        .await?;
+    println!("3. WebAuthn challenge created successfully!");
+
+    let allow_credentials: Vec<Value> = match user_id {
+        Some(user_id) => database_layer
+            .query()
+            .passkey
+            .list(user_id)
+            .await?
+            .into_iter()
+            .map(|passkey| json!({ "type": "public-key", "id": passkey.credential_id }))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let public_key = json!({
+        "challenge": webauthn_challenge.challenge,
+        "rpId": app_state.config.webauthn_rp_id,
+        "timeout": 300000,
+        "allowCredentials": allow_credentials,
+        "userVerification": "preferred",
+    });
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            challenge_id: webauthn_challenge.id.id.to_raw(),
+            public_key,
+        }),
+    ))
+}
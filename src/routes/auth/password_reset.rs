@@ -1,42 +1,169 @@
-use axum::{Extension, Json};
+use axum::{extract::State, Extension, Json};
+use chrono::Utc;
 use hyper::StatusCode;
 use serde::{Deserialize, Serialize};
-use surrealdb::sql::Thing;
 use validator::Validate;
 
 use crate::{
-    errors::{auth::PasswordResetError, response::ApiError},
-    services::{database::DatabaseLayer, email::EmailLayer},
+    errors::{auth::PasswordResetError, response::ApiError, CommonError},
+    services::{
+        breached_password::BreachedPasswordMode, database::DatabaseLayer, email::EmailLayer,
+        i18n::with_locale,
+    },
+    setup::AppState,
+    utils::crypto::{hash_password, verify_string_hash},
 };
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct RoutePayload {
-    password_reset_request_id: Thing,
+    #[validate(email)]
+    email: String,
+    password_reset_request_id_hash: String,
+    password: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RouteOutput {
     message: String,
+    password_breached: bool,
 }
 
 #[axum::debug_handler]
 pub async fn password_reset(
+    State(app_state): State<AppState>,
     Extension(database_layer): Extension<DatabaseLayer>,
     Extension(email_layer): Extension<EmailLayer>,
     Json(payload): Json<RoutePayload>,
 ) -> Result<(StatusCode, Json<RouteOutput>), ApiError<PasswordResetError>> {
     // 1. Validate payload input
     let payload_instance = RoutePayload {
-        password_reset_request_id: payload.password_reset_request_id.clone(),
+        email: payload.email.clone(),
+        password_reset_request_id_hash: payload.password_reset_request_id_hash.clone(),
+        password: payload.password.clone(),
     };
 
     payload_instance.validate()?;
-    println!("Validation passed successfully.");
+
+    app_state
+        .config
+        .password_policy
+        .check(&payload.password, &payload.email)
+        .map_err(|errors| ApiError(PasswordResetError::WeakPassword(errors)))?;
+
+    let password_breached = app_state
+        .breached_passwords
+        .is_breached(&payload.password)
+        .await;
+
+    if password_breached && app_state.breached_passwords.mode == BreachedPasswordMode::Reject {
+        return Err(ApiError(PasswordResetError::BreachedPassword));
+    }
+    println!("1. Validation passed successfully!");
+
+    // 2. Retrieve user and the matching password reset request
+
+    let user = match database_layer.query().user.get(payload.email.clone()).await {
+        Ok(user) => user,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(PasswordResetError::InvalidEmail));
+        }
+        Err(err) => {
+            return Err(ApiError(PasswordResetError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+
+    let password_reset_request = match database_layer
+        .query()
+        .password_reset_request
+        .get(user.id.clone())
+        .await
+    {
+        Ok(password_reset_request) => password_reset_request,
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(PasswordResetError::InvalidToken));
+        }
+        Err(err) => {
+            return Err(ApiError(PasswordResetError::Common(CommonError::Database(
+                err,
+            ))))
+        }
+    };
+    println!("2. Password reset request retrieved successfully!");
+
+    // 3. Validate the token and its expiration
+
+    let password_reset_request_id_matches = verify_string_hash(
+        password_reset_request.id.id.to_string(),
+        payload.password_reset_request_id_hash.clone(),
+    );
+
+    if !password_reset_request_id_matches {
+        return Err(ApiError(PasswordResetError::InvalidToken));
+    }
+
+    if *password_reset_request.expires_at < Utc::now() {
+        return Err(ApiError(PasswordResetError::TokenExpired));
+    }
+    println!("3. Password reset token validated successfully!");
+
+    // 4. Remove password reset request before using it, so concurrent requests can't both
+    // change the password
+
+    let password_reset_request_consumed = database_layer
+        .query()
+        .password_reset_request
+        .consume(password_reset_request.id.clone(), user.id.clone())
+        .await?;
+
+    if !password_reset_request_consumed {
+        return Err(ApiError(PasswordResetError::InvalidToken));
+    }
+    println!("4. Password reset request removed successfully!");
+
+    // 5. Update user password, proving ownership of the email also lifts a signin lock
+
+    let password_hash = hash_password(payload.password.clone()).await?;
+
+    let user = database_layer
+        .query()
+        .user
+        .update_password(user.id, password_hash)
+        .await?;
+
+    database_layer.query().user.unlock(user.id.clone()).await?;
+
+    database_layer
+        .query()
+        .failed_signin
+        .clear_for_user(user.id.clone())
+        .await?;
+    println!("5. User password updated successfully!");
+
+    // 6. Remove all user sessions
+
+    database_layer
+        .query()
+        .session
+        .invalidate_all(user.id.clone())
+        .await?;
+    println!("6. User sessions invalidated successfully!");
+
+    // 7. Send email to user confirming the password change
+
+    with_locale(
+        user.preferred_locale(),
+        email_layer.send_password_reset_confirmation(user.email.clone()),
+    )
+    .await?;
+    println!("7. Password change confirmation email sent successfully!");
 
     Ok((
         StatusCode::OK,
         Json(RouteOutput {
             message: String::from("Password reset completed successfully"),
+            password_breached,
         }),
     ))
 }
@@ -5,7 +5,7 @@ use validator::Validate;
 
 use crate::{
     errors::{auth::PasswordResetRequestError, response::ApiError},
-    services::{database::DatabaseLayer, email::EmailLayer},
+    services::{database::DatabaseLayer, email::EmailLayer, i18n::with_locale},
     utils::crypto::{generate_uuid, hash_string},
 };
 
@@ -52,9 +52,11 @@ pub async fn password_reset_request(
     println!("Password reset request creation completed successfully.");
 
     // 3. Send an email with the details on how to reset the password
-    email_layer
-        .send_password_reset(payload.email, id_hash)
-        .await?;
+    with_locale(
+        user.preferred_locale(),
+        email_layer.send_password_reset(payload.email, id_hash),
+    )
+    .await?;
     println!("Password reset email sent successfully!");
 
     Ok((
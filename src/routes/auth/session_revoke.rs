@@ -0,0 +1,47 @@
+use axum::{extract::Path, Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::{
+    errors::{auth::SessionsError, response::ApiError, CommonError},
+    extractors::AuthSession,
+    services::database::DatabaseLayer,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn session_revoke(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Path(session_id): Path<String>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<SessionsError>> {
+    // 1. Remove the session, only if it belongs to the current user
+
+    let session_id = Thing::from((String::from("session"), session_id));
+
+    match database_layer
+        .query()
+        .session
+        .remove_for_user(session_id, user.id)
+        .await
+    {
+        Ok(()) => (),
+        Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            return Err(ApiError(SessionsError::SessionNotFound));
+        }
+        Err(err) => return Err(ApiError(SessionsError::Common(CommonError::Database(err)))),
+    };
+    println!("1. Session revoked successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Session revoked successfully"),
+        }),
+    ))
+}
@@ -0,0 +1,54 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Datetime;
+
+use crate::{
+    errors::{auth::SessionsError, response::ApiError},
+    extractors::AuthSession,
+    services::database::DatabaseLayer,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionOutput {
+    id: String,
+    device_label: Option<String>,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: Datetime,
+    last_accessed_at: Datetime,
+    current: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    sessions: Vec<SessionOutput>,
+}
+
+#[axum::debug_handler]
+pub async fn sessions(
+    AuthSession { session, user }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<SessionsError>> {
+    // 1. Retrieve all active user sessions
+
+    let user_sessions = database_layer.query().session.list(user.id).await?;
+    println!("1. User sessions retrieved successfully!");
+
+    // 2. Map sessions to the output, the session id is the token hash so it is safe to expose
+
+    let sessions = user_sessions
+        .into_iter()
+        .map(|user_session| SessionOutput {
+            current: user_session.id == session.id,
+            id: user_session.id.id.to_raw(),
+            device_label: user_session.device_label,
+            user_agent: user_session.user_agent,
+            ip_address: user_session.ip_address,
+            created_at: user_session.created_at,
+            last_accessed_at: user_session.last_accessed_at,
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(RouteOutput { sessions })))
+}
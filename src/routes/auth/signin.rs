@@ -1,17 +1,33 @@
 use axum::{
+    extract::State,
     response::{IntoResponse, Response},
     Extension, Json,
 };
+use chrono::{Duration, Utc};
 use hyper::{header::SET_COOKIE, StatusCode};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use surrealdb::sql::{Datetime, Thing};
+
 use crate::{
     errors::{auth::SigninError, response::ApiError},
-    services::database::DatabaseLayer,
-    utils::{cookies::set_session_cookie, crypto::verify_password_hash},
+    extractors::ClientInfo,
+    services::{
+        database::{user::User, DatabaseLayer},
+        email::{EmailError, EmailLayer},
+        i18n::with_locale,
+    },
+    setup::{AppState, SigninLockoutPolicy, VerificationAccess},
+    utils::{
+        cookies::set_session_cookie,
+        crypto::{generate_token, verify_password_hash},
+    },
 };
 
+// Second factor codes a pending session can try before it's removed
+const MAX_SECOND_FACTOR_ATTEMPTS: i64 = 5;
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct RoutePayload {
     #[validate(email)]
@@ -22,12 +38,17 @@ pub struct RoutePayload {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RouteOutput {
     message: String,
+    two_factor_required: bool,
+    two_factor_methods: Vec<String>,
+    email_verification_required: bool,
 }
 
-// TODO: Add 2FA
 #[axum::debug_handler]
 pub async fn signin(
+    State(app_state): State<AppState>,
+    client_info: ClientInfo,
     Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
     Json(payload): Json<RoutePayload>,
     // TODO: Same stuff with the Response type as signup
 ) -> Result<(StatusCode, Response), ApiError<SigninError>> {
@@ -40,11 +61,36 @@ pub async fn signin(
     payload_instance.validate()?;
     println!("1. Validation passed successfully!");
 
-    // 2. Retrive user from database
+    // 2. Throttle addresses that keep failing, regardless of the targeted account
+
+    let lockout_policy = &app_state.config.signin_lockout;
+    let attempt_window_start =
+        Utc::now() - Duration::minutes(lockout_policy.attempt_window_minutes);
+
+    if let Some(ip_address) = client_info.ip_address.clone() {
+        let failed_attempts = database_layer
+            .query()
+            .failed_signin
+            .count_for_ip_address(ip_address, attempt_window_start)
+            .await?;
+
+        if failed_attempts >= lockout_policy.max_failed_attempts_per_ip {
+            return Err(ApiError(SigninError::TooManyAttempts));
+        }
+    }
+    println!("2. Address attempt limit check completed successfully!");
+
+    // 3. Retrive user from database
 
     let user = match database_layer.query().user.get(payload.email.clone()).await {
         Ok(user) => user,
         Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(_))) => {
+            database_layer
+                .query()
+                .failed_signin
+                .create(payload.email.clone(), client_info.ip_address.clone(), None)
+                .await?;
+
             return Err(ApiError(SigninError::InvalidCredentials));
         }
         Err(err) => {
@@ -53,32 +99,101 @@ pub async fn signin(
             )))
         }
     };
-    println!("2. User existence check completed successfully!");
+    println!("3. User existence check completed successfully!");
 
-    // 3. Verify password
+    // 4. Verify password, failures count towards locking the account
 
     let password_matches =
-        verify_password_hash(payload.password.clone(), user.password_hash).await?;
+        verify_password_hash(payload.password.clone(), user.password_hash.clone()).await?;
+
+    // Locked accounts look like wrong credentials, even with the right password, so the lock
+    // can't be used to probe for accounts or passwords. The owner learns about it by email
+    if user
+        .locked_until
+        .as_ref()
+        .is_some_and(|locked_until| **locked_until > Utc::now())
+    {
+        return Err(ApiError(SigninError::InvalidCredentials));
+    }
 
     if !password_matches {
+        let account_locked = register_failed_attempt(
+            &database_layer,
+            &email_layer,
+            lockout_policy,
+            &user,
+            client_info.ip_address.clone(),
+        )
+        .await?;
+
+        if account_locked {
+            return Err(ApiError(SigninError::AccountLocked));
+        }
+
         return Err(ApiError(SigninError::InvalidCredentials));
     }
-    println!("3. Password confirmed successfully!");
 
-    // 4. Create a session in database
+    database_layer
+        .query()
+        .failed_signin
+        .clear_for_user(user.id.clone())
+        .await?;
 
-    let session = database_layer.query().session.create(user.id, true).await?;
-    println!("4. Session created successfully!");
+    if user.lock_count > 0 {
+        database_layer.query().user.unlock(user.id.clone()).await?;
+    }
+
+    // Checked after the password so the deletion status isn't revealed to anyone else
+    if user.deletion_scheduled_at.is_some() {
+        return Err(ApiError(SigninError::AccountPendingDeletion));
+    }
+    println!("4. Password confirmed successfully!");
+
+    // 5. Apply the email verification policy, restricted users only get an unauthorized session
+
+    let verification_access = app_state.config.email_verification_policy.access(&user);
+
+    if verification_access == VerificationAccess::Denied {
+        return Err(ApiError(SigninError::AccountNotVerified));
+    }
 
-    // 5. Create a session cookie
+    let email_verification_required = verification_access == VerificationAccess::Restricted;
+    println!("5. Email verification policy applied successfully!");
 
-    let cookie = set_session_cookie(session.id.clone().id.to_string(), true);
-    println!("Session cookie created successfully!");
+    // 6. Create a session in database, users with 2FA enabled get an unauthorized session that
+    // gets replaced once the second factor is provided through `signin_two_factor` or a passkey
+
+    let two_factor_methods = two_factor_methods(&database_layer, &user).await?;
+    let two_factor_required = !two_factor_methods.is_empty();
+    let authorized = !two_factor_required && !email_verification_required;
+
+    let (_session, session_token) = database_layer
+        .query()
+        .session
+        .create(user.id, authorized, client_info.session_metadata())
+        .await?;
+    println!("6. Session created successfully!");
+
+    // 7. Create a session cookie
+
+    let cookie = set_session_cookie(session_token, authorized);
+    println!("7. Session cookie created successfully!");
+
+    let message = if two_factor_required {
+        String::from("Two-factor authentication required")
+    } else if email_verification_required {
+        String::from("Email verification required")
+    } else {
+        String::from("Signin completed successfully!")
+    };
 
     let mut response = (
         StatusCode::OK,
         Json(RouteOutput {
-            message: String::from("Signin completed successfully!"),
+            message,
+            two_factor_required,
+            two_factor_methods,
+            email_verification_required,
         }),
     )
         .into_response();
@@ -89,3 +204,115 @@ pub async fn signin(
 
     Ok((StatusCode::OK, response))
 }
+
+// Second factors the user can complete a pending signin with, empty when 2FA isn't set up
+pub async fn two_factor_methods(
+    database_layer: &DatabaseLayer,
+    user: &User,
+) -> Result<Vec<String>, surrealdb::Error> {
+    let mut two_factor_methods = Vec::new();
+
+    if user.totp_enabled {
+        two_factor_methods.push(String::from("totp"));
+    }
+
+    let passkeys = database_layer.query().passkey.list(user.id.clone()).await?;
+
+    if !passkeys.is_empty() {
+        two_factor_methods.push(String::from("passkey"));
+    }
+
+    Ok(two_factor_methods)
+}
+
+// Counts a second factor attempt against the pending session, once the attempts are used up the
+// session is removed and the signin has to start over with the password
+pub async fn consume_second_factor_attempt(
+    database_layer: &DatabaseLayer,
+    session_id: Thing,
+) -> Result<(), ApiError<SigninError>> {
+    let attempt_allowed = database_layer
+        .query()
+        .session
+        .consume_second_factor_attempt(session_id.clone(), MAX_SECOND_FACTOR_ATTEMPTS)
+        .await?;
+
+    if !attempt_allowed {
+        // Concurrent attempts may have removed the session already
+        if let Err(err) = database_layer.query().session.remove(session_id).await {
+            println!("Failed to remove the pending session: {}", err);
+        }
+
+        return Err(ApiError(SigninError::TooManyAttempts));
+    }
+
+    Ok(())
+}
+
+// Records a failed signin attempt and locks the account once the policy threshold is reached,
+// returns whether the account got locked. Wrong second factors and signin codes count as well
+pub async fn register_failed_attempt<E>(
+    database_layer: &DatabaseLayer,
+    email_layer: &EmailLayer,
+    lockout_policy: &SigninLockoutPolicy,
+    user: &User,
+    ip_address: Option<String>,
+) -> Result<bool, ApiError<E>>
+where
+    ApiError<E>: From<surrealdb::Error> + From<EmailError>,
+{
+    database_layer
+        .query()
+        .failed_signin
+        .create(user.email.clone(), ip_address, Some(user.id.clone()))
+        .await?;
+
+    let attempt_window_start =
+        Utc::now() - Duration::minutes(lockout_policy.attempt_window_minutes);
+
+    let failed_attempts = database_layer
+        .query()
+        .failed_signin
+        .count_for_user(user.id.clone(), attempt_window_start)
+        .await?;
+
+    if failed_attempts < lockout_policy.max_failed_attempts {
+        return Ok(false);
+    }
+
+    // Every consecutive lock doubles the previous lock duration
+    let lock_minutes = lockout_policy
+        .base_lock_minutes
+        .saturating_mul(1 << user.lock_count.min(30))
+        .min(lockout_policy.max_lock_minutes);
+    let locked_until = Datetime::from(Utc::now() + Duration::minutes(lock_minutes));
+
+    database_layer
+        .query()
+        .user
+        .lock(user.id.clone(), locked_until, user.lock_count + 1)
+        .await?;
+
+    // The attempts that led to the lock shouldn't count towards the next one
+    database_layer
+        .query()
+        .failed_signin
+        .clear_for_user(user.id.clone())
+        .await?;
+
+    let unlock_token = generate_token();
+
+    database_layer
+        .query()
+        .account_unlock
+        .create(&unlock_token, user.id.clone())
+        .await?;
+
+    with_locale(
+        user.preferred_locale(),
+        email_layer.send_account_locked(user.email.clone(), lock_minutes, unlock_token),
+    )
+    .await?;
+
+    Ok(true)
+}
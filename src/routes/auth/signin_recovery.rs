@@ -0,0 +1,155 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use hyper::{header::SET_COOKIE, StatusCode};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{auth::SigninError, response::ApiError},
+    extractors::{AnySession, ClientInfo},
+    services::{database::DatabaseLayer, email::EmailLayer},
+    setup::{AppState, VerificationAccess},
+    utils::{cookies::set_session_cookie, crypto::verify_password_hash},
+};
+
+use super::signin::{consume_second_factor_attempt, register_failed_attempt, two_factor_methods};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(length(min = 1))]
+    recovery_code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+// Alternative second signin phase for users who lost access to their authenticator app or passkey
+#[axum::debug_handler]
+pub async fn signin_recovery(
+    State(app_state): State<AppState>,
+    client_info: ClientInfo,
+    AnySession { session, user }: AnySession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Response), ApiError<SigninError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        recovery_code: payload.recovery_code.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Check if the session is waiting for the second factor
+
+    if session.authorized || two_factor_methods(&database_layer, &user).await?.is_empty() {
+        return Err(ApiError(SigninError::TwoFactorNotRequired));
+    }
+
+    if let Some(locked_until) = &user.locked_until {
+        if **locked_until > Utc::now() {
+            return Err(ApiError(SigninError::AccountLocked));
+        }
+    }
+
+    // Every attempt costs an argon2 verification per stored code, so attempts are limited like
+    // TOTP codes
+    consume_second_factor_attempt(&database_layer, session.id.clone()).await?;
+    println!("2. Second factor attempt registered successfully!");
+
+    // 3. Find the matching recovery code and consume it
+
+    let recovery_code = payload.recovery_code.trim().to_lowercase();
+
+    let recovery_codes = database_layer
+        .query()
+        .recovery_code
+        .list(user.id.clone())
+        .await?;
+
+    let mut matching_code = None;
+    for stored_code in &recovery_codes {
+        if verify_password_hash(recovery_code.clone(), stored_code.code_hash.clone()).await? {
+            matching_code = Some(stored_code.id.clone());
+            break;
+        }
+    }
+
+    let consumed = match matching_code {
+        Some(recovery_code_id) => {
+            database_layer
+                .query()
+                .recovery_code
+                .consume(recovery_code_id)
+                .await?
+        }
+        None => false,
+    };
+
+    if !consumed {
+        let account_locked = register_failed_attempt(
+            &database_layer,
+            &email_layer,
+            &app_state.config.signin_lockout,
+            &user,
+            client_info.ip_address.clone(),
+        )
+        .await?;
+
+        if account_locked {
+            return Err(ApiError(SigninError::AccountLocked));
+        }
+
+        return Err(ApiError(SigninError::InvalidRecoveryCode));
+    }
+    println!("3. Recovery code consumed successfully!");
+
+    // 4. Replace the pending session, unverified users stay restricted by the verification policy
+
+    let authorized = match app_state.config.email_verification_policy.access(&user) {
+        VerificationAccess::Full => true,
+        VerificationAccess::Restricted => false,
+        VerificationAccess::Denied => return Err(ApiError(SigninError::AccountNotVerified)),
+    };
+
+    database_layer.query().session.remove(session.id).await?;
+
+    let (_session, session_token) = database_layer
+        .query()
+        .session
+        .create(user.id, authorized, client_info.session_metadata())
+        .await?;
+    println!("4. Session created successfully!");
+
+    // 5. Notify the user that a recovery code was used
+
+    email_layer
+        .send_recovery_code_used(user.email, recovery_codes.len() - 1)
+        .await?;
+    println!("5. Recovery code notification email sent successfully!");
+
+    // 6. Create a session cookie
+
+    let cookie = set_session_cookie(session_token, authorized);
+
+    let message = if authorized {
+        String::from("Signin completed successfully!")
+    } else {
+        String::from("Email verification required")
+    };
+
+    let mut response = (StatusCode::OK, Json(RouteOutput { message })).into_response();
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+
+    Ok((StatusCode::OK, response))
+}
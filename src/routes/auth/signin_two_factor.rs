@@ -0,0 +1,147 @@
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::Utc;
+use hyper::{header::SET_COOKIE, StatusCode};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    errors::{auth::SigninError, response::ApiError},
+    extractors::{AnySession, ClientInfo},
+    services::{database::DatabaseLayer, email::EmailLayer},
+    setup::{AppState, VerificationAccess},
+    utils::{
+        cookies::set_session_cookie,
+        crypto::decrypt_secret,
+        totp::verify_totp_code,
+        validation::{validate_numeric_code_format, validate_numeric_code_length},
+    },
+};
+
+use super::signin::{consume_second_factor_attempt, register_failed_attempt};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(custom(function = "validate_numeric_code_length"))]
+    #[validate(custom(function = "validate_numeric_code_format"))]
+    code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+// Second signin phase, upgrades the unauthorized session created by `signin` for 2FA users
+#[axum::debug_handler]
+pub async fn signin_two_factor(
+    State(app_state): State<AppState>,
+    client_info: ClientInfo,
+    AnySession { session, user }: AnySession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Extension(email_layer): Extension<EmailLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Response), ApiError<SigninError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        code: payload.code.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Check if the session is waiting for the second factor
+
+    let encrypted_secret = match (
+        session.authorized,
+        user.totp_enabled,
+        user.totp_secret.clone(),
+    ) {
+        (false, true, Some(encrypted_secret)) => encrypted_secret,
+        _ => return Err(ApiError(SigninError::TwoFactorNotRequired)),
+    };
+
+    if let Some(locked_until) = &user.locked_until {
+        if **locked_until > Utc::now() {
+            return Err(ApiError(SigninError::AccountLocked));
+        }
+    }
+
+    consume_second_factor_attempt(&database_layer, session.id.clone()).await?;
+    println!("2. Second factor attempt registered successfully!");
+
+    // 3. Verify the TOTP code, wrong codes count towards locking the account and used codes
+    // can't be replayed
+
+    let secret = decrypt_secret(&encrypted_secret, &app_state.config.totp_encryption_key)?;
+
+    let totp_step = match verify_totp_code(secret, &payload.code) {
+        Some(totp_step) => totp_step,
+        None => {
+            let account_locked = register_failed_attempt(
+                &database_layer,
+                &email_layer,
+                &app_state.config.signin_lockout,
+                &user,
+                client_info.ip_address.clone(),
+            )
+            .await?;
+
+            if account_locked {
+                return Err(ApiError(SigninError::AccountLocked));
+            }
+
+            return Err(ApiError(SigninError::InvalidTwoFactorCode));
+        }
+    };
+
+    let step_accepted = database_layer
+        .query()
+        .user
+        .accept_totp_step(user.id.clone(), totp_step)
+        .await?;
+
+    if !step_accepted {
+        return Err(ApiError(SigninError::InvalidTwoFactorCode));
+    }
+    println!("3. TOTP code verified successfully!");
+
+    // 4. Replace the pending session, unverified users stay restricted by the verification policy
+
+    let authorized = match app_state.config.email_verification_policy.access(&user) {
+        VerificationAccess::Full => true,
+        VerificationAccess::Restricted => false,
+        VerificationAccess::Denied => return Err(ApiError(SigninError::AccountNotVerified)),
+    };
+
+    database_layer.query().session.remove(session.id).await?;
+
+    let (_session, session_token) = database_layer
+        .query()
+        .session
+        .create(user.id, authorized, client_info.session_metadata())
+        .await?;
+    println!("4. Session created successfully!");
+
+    // 5. Create a session cookie
+
+    let cookie = set_session_cookie(session_token, authorized);
+    println!("5. Session cookie created successfully!");
+
+    let message = if authorized {
+        String::from("Signin completed successfully!")
+    } else {
+        String::from("Email verification required")
+    };
+
+    let mut response = (StatusCode::OK, Json(RouteOutput { message })).into_response();
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+
+    Ok((StatusCode::OK, response))
+}
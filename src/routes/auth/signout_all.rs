@@ -0,0 +1,51 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use hyper::{header::SET_COOKIE, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{auth::SignoutError, response::ApiError},
+    extractors::AuthSession,
+    services::database::DatabaseLayer,
+    utils::cookies::remove_session_cookie,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+}
+
+#[axum::debug_handler]
+pub async fn signout_all(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+) -> Result<(StatusCode, Response), ApiError<SignoutError>> {
+    // 1. Remove all user sessions, including the current one
+
+    database_layer
+        .query()
+        .session
+        .invalidate_all(user.id)
+        .await?;
+    println!("1. User sessions invalidated successfully!");
+
+    // 2. Expire the session cookie
+
+    let cookie = remove_session_cookie();
+
+    let mut response = (
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Signed out of all sessions successfully!"),
+        }),
+    )
+        .into_response();
+
+    response
+        .headers_mut()
+        .insert(SET_COOKIE, cookie.to_string().parse().unwrap());
+
+    Ok((StatusCode::OK, response))
+}
@@ -10,12 +10,19 @@ use validator::Validate;
 
 use crate::{
     errors::{auth::SignupError, response::ApiError},
-    services::{database::DatabaseLayer, email::EmailLayer},
+    extractors::ClientInfo,
+    services::breached_password::BreachedPasswordMode,
+    services::{
+        database::{is_unique_violation, DatabaseLayer},
+        email::EmailLayer,
+        i18n::current_locale,
+    },
     setup::AppState,
     utils::{
         cookies::set_session_cookie,
         crypto::{hash_password, hash_string},
         random::generate_random_code,
+        validation::normalize_email,
     },
 };
 
@@ -23,39 +30,52 @@ use crate::{
 pub struct RoutePayload {
     #[validate(email)]
     email: String,
-    // TODO: custom password validation functions
     password: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RouteOutput {
     message: String,
+    password_breached: bool,
+    verification_email_sent: bool,
 }
 
-// TODO: In case if any of the steps fails the previous steps need to be reverted
-// For example: If user gets created, but email-verification creation fails it needs to reverse the
-// user creation and throw the INTERNAL_SERVER_ERROR
-// The whole process should be handled manually since even if the email doesn't get sent to the
-// user the user record should still stay in the database as the email-verification request can be
-// created at any time
 #[axum::debug_handler]
 pub async fn signup(
     State(app_state): State<AppState>,
+    client_info: ClientInfo,
     Extension(database_layer): Extension<DatabaseLayer>,
     Extension(email_layer): Extension<EmailLayer>,
-    Json(payload): Json<RoutePayload>,
+    Json(mut payload): Json<RoutePayload>,
     // TODO: Add a custom SignupResponse type so it includes Json<RouteOutput> and the cookie, etc.
 ) -> Result<(StatusCode, Response), ApiError<SignupError>> {
     // 1. Validate payload input
+    payload.email = normalize_email(&payload.email);
+
     let payload_instance = RoutePayload {
         email: payload.email.clone(),
         password: payload.password.clone(),
     };
 
     payload_instance.validate()?;
+
+    app_state
+        .config
+        .password_policy
+        .check(&payload.password, &payload.email)
+        .map_err(|errors| ApiError(SignupError::WeakPassword(errors)))?;
+
+    let password_breached = app_state
+        .breached_passwords
+        .is_breached(&payload.password)
+        .await;
+
+    if password_breached && app_state.breached_passwords.mode == BreachedPasswordMode::Reject {
+        return Err(ApiError(SignupError::BreachedPassword));
+    }
     println!("1. Validation passed successfully!");
 
-    // 2. Check if the email is available
+    // 2. Check if the email is available, the unique index still guards concurrent signups
 
     let user_exists = database_layer
         .query()
@@ -68,61 +88,68 @@ pub async fn signup(
     }
     println!("2. Email availability check completed successfully!");
 
-    // 3. Create a new user in the database
+    // 3. Create the user, the email verification and an unauthorized session in one transaction
 
     let password_hash = hash_password(payload.password.clone()).await?;
     println!("3. Password hashed successfully!");
 
-    let user = database_layer
-        .query()
-        .user
-        .create(payload.email.clone(), password_hash)
-        .await?;
-    println!("4. User created successfully!");
-
-    // 4. Create email verification in the database
-
     let verification_code = generate_random_code(6);
     let verification_code_hash = hash_string(verification_code.clone());
 
-    let email_verification = database_layer
+    let (user, email_verification, _session, session_token) = match database_layer
         .query()
-        .email_verification
-        .create(
-            verification_code_hash,
+        .user
+        .create_for_signup(
             payload.email.clone(),
-            user.id.clone(),
+            password_hash,
+            verification_code_hash,
+            current_locale().code().to_string(),
+            client_info.session_metadata(),
         )
-        .await?;
-    println!("5. Email verification created successfully!");
+        .await
+    {
+        Ok(created) => created,
+        Err(err) if is_unique_violation(&err, "user_email") => {
+            return Err(ApiError(SignupError::EmailAlreadyExists))
+        }
+        Err(err) => return Err(err.into()),
+    };
+    println!("4. User, email verification and session created successfully!");
 
-    // 5. Send email verification email
+    // 4. Send email verification email, an email that couldn't be queued is recorded and retried
+    // in the background instead of failing the signup of an already created user
 
     let token_hash = hash_string(email_verification.id.id.clone().to_string());
 
-    email_layer
+    let verification_email_sent = match email_layer
         .send_email_verification(payload.email, verification_code, token_hash)
-        .await?;
-    println!("6. Email verification email sent successfully!");
-
-    // 6. Create unauthorized session in the database
+        .await
+    {
+        Ok(_) => true,
+        Err(err) => {
+            println!("Failed to send email verification email: {}", err);
+            false
+        }
+    };
 
-    let session = database_layer
+    database_layer
         .query()
-        .session
-        .create(user.id, false)
+        .email_verification
+        .record_dispatch(user.id, verification_email_sent)
         .await?;
-    println!("7. Unauthorized session created successfully!");
+    println!("5. Email verification dispatch recorded successfully!");
 
-    // 7. Create a session cookie and add it to response
+    // 5. Create a session cookie and add it to response
 
-    let cookie = set_session_cookie(session.id.clone().id.to_string(), false);
-    println!("8. Unauthorized session cookie created successfully!");
+    let cookie = set_session_cookie(session_token, false);
+    println!("6. Unauthorized session cookie created successfully!");
 
     let mut response = (
         StatusCode::OK,
         Json(RouteOutput {
             message: String::from("Signup completed successfully!"),
+            password_breached,
+            verification_email_sent,
         }),
     )
         .into_response();
@@ -0,0 +1,130 @@
+use axum::{extract::State, Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+use validator::Validate;
+
+use crate::{
+    errors::{auth::TwoFactorError, response::ApiError},
+    extractors::AuthSession,
+    services::database::DatabaseLayer,
+    setup::AppState,
+    utils::{
+        crypto::{decrypt_secret, hash_password},
+        random::generate_recovery_codes,
+        totp::verify_totp_code,
+        validation::{validate_numeric_code_format, validate_numeric_code_length},
+    },
+};
+
+pub const RECOVERY_CODE_COUNT: usize = 10;
+// Codes that can be tried against a pending secret before the setup has to be started again
+const MAX_SETUP_ATTEMPTS: i64 = 5;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RoutePayload {
+    #[validate(custom(function = "validate_numeric_code_length"))]
+    #[validate(custom(function = "validate_numeric_code_format"))]
+    code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    recovery_codes: Vec<String>,
+}
+
+#[axum::debug_handler]
+pub async fn two_factor_confirm(
+    State(app_state): State<AppState>,
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+    Json(payload): Json<RoutePayload>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<TwoFactorError>> {
+    // 1. Validate payload input
+    let payload_instance = RoutePayload {
+        code: payload.code.clone(),
+    };
+
+    payload_instance.validate()?;
+    println!("1. Validation passed successfully!");
+
+    // 2. Check if 2FA setup was started
+
+    if user.totp_enabled {
+        return Err(ApiError(TwoFactorError::AlreadyEnabled));
+    }
+
+    let encrypted_secret = match user.totp_secret {
+        Some(encrypted_secret) => encrypted_secret,
+        None => return Err(ApiError(TwoFactorError::NotSetUp)),
+    };
+
+    let attempt_allowed = database_layer
+        .query()
+        .user
+        .consume_totp_setup_attempt(user.id.clone(), MAX_SETUP_ATTEMPTS)
+        .await?;
+
+    if !attempt_allowed {
+        return Err(ApiError(TwoFactorError::TooManyAttempts));
+    }
+    println!("2. Two-factor setup attempt counted successfully!");
+
+    // 3. Verify the code against the stored secret
+
+    let secret = decrypt_secret(&encrypted_secret, &app_state.config.totp_encryption_key)?;
+
+    let totp_step = match verify_totp_code(secret, &payload.code) {
+        Some(totp_step) => totp_step,
+        None => return Err(ApiError(TwoFactorError::InvalidCode)),
+    };
+    println!("3. TOTP code verified successfully!");
+
+    // 4. Enable 2FA
+
+    database_layer
+        .query()
+        .user
+        .enable_totp(user.id.clone(), totp_step)
+        .await?;
+    println!("4. Two-factor authentication enabled successfully!");
+
+    // 5. Generate recovery codes
+
+    let recovery_codes = issue_recovery_codes(&database_layer, user.id).await?;
+    println!("5. Recovery codes created successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Two-factor authentication enabled successfully"),
+            recovery_codes,
+        }),
+    ))
+}
+
+// Replaces the recovery codes of the user with new ones, only their hashes are stored. The codes
+// stand in for any second factor, so they're issued with the first TOTP or passkey setup
+pub async fn issue_recovery_codes<E>(
+    database_layer: &DatabaseLayer,
+    user_id: Thing,
+) -> Result<Vec<String>, ApiError<E>>
+where
+    ApiError<E>: From<surrealdb::Error> + From<argon2::password_hash::Error>,
+{
+    let recovery_codes = generate_recovery_codes(RECOVERY_CODE_COUNT);
+
+    let mut recovery_code_hashes = Vec::with_capacity(recovery_codes.len());
+    for recovery_code in &recovery_codes {
+        recovery_code_hashes.push(hash_password(recovery_code.clone()).await?);
+    }
+
+    database_layer
+        .query()
+        .recovery_code
+        .replace_all(user_id, recovery_code_hashes)
+        .await?;
+
+    Ok(recovery_codes)
+}
@@ -0,0 +1,43 @@
+use axum::{Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{auth::TwoFactorError, response::ApiError},
+    extractors::AuthSession,
+    services::database::DatabaseLayer,
+};
+
+use super::{signin::two_factor_methods, two_factor_confirm::issue_recovery_codes};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    message: String,
+    recovery_codes: Vec<String>,
+}
+
+// Regenerates recovery codes, all previously issued codes stop working
+#[axum::debug_handler]
+pub async fn two_factor_recovery_codes(
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<TwoFactorError>> {
+    // 1. Check if a second factor is set up
+
+    if two_factor_methods(&database_layer, &user).await?.is_empty() {
+        return Err(ApiError(TwoFactorError::NotEnabled));
+    }
+
+    // 2. Generate new recovery codes and replace the old ones
+
+    let recovery_codes = issue_recovery_codes(&database_layer, user.id).await?;
+    println!("2. Recovery codes regenerated successfully!");
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            message: String::from("Recovery codes regenerated successfully"),
+            recovery_codes,
+        }),
+    ))
+}
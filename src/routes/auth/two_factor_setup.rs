@@ -0,0 +1,57 @@
+use axum::{extract::State, Extension, Json};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{auth::TwoFactorError, response::ApiError},
+    extractors::AuthSession,
+    services::database::DatabaseLayer,
+    setup::AppState,
+    utils::{
+        crypto::encrypt_secret,
+        totp::{build_totp, generate_totp_secret},
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    secret: String,
+    otpauth_url: String,
+}
+
+#[axum::debug_handler]
+pub async fn two_factor_setup(
+    State(app_state): State<AppState>,
+    AuthSession { user, .. }: AuthSession,
+    Extension(database_layer): Extension<DatabaseLayer>,
+) -> Result<(StatusCode, Json<RouteOutput>), ApiError<TwoFactorError>> {
+    // 1. Check if 2FA isn't enabled already
+
+    if user.totp_enabled {
+        return Err(ApiError(TwoFactorError::AlreadyEnabled));
+    }
+
+    // 2. Generate a new secret and store it encrypted, it stays inactive until confirmed
+
+    let secret = generate_totp_secret();
+    let encrypted_secret = encrypt_secret(&secret, &app_state.config.totp_encryption_key)?;
+
+    database_layer
+        .query()
+        .user
+        .set_totp_secret(user.id, encrypted_secret)
+        .await?;
+    println!("2. TOTP secret stored successfully!");
+
+    // 3. Return the secret so it can be added to an authenticator app
+
+    let totp = build_totp(secret, user.email);
+
+    Ok((
+        StatusCode::OK,
+        Json(RouteOutput {
+            secret: totp.get_secret_base32(),
+            otpauth_url: totp.get_url(),
+        }),
+    ))
+}
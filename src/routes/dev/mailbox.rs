@@ -0,0 +1,139 @@
+use axum::{extract::Query, Extension, Json};
+use hyper::StatusCode;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    services::email::{EmailLayer, EmailMessage},
+    utils::validation::normalize_email,
+};
+
+lazy_static! {
+    static ref LINK_REGEX: Regex = Regex::new(r#"https?://[^\s<>"']+"#).unwrap();
+    static ref REFERENCE_REGEX: Regex = Regex::new(r"\b[0-9a-f]{64}\b").unwrap();
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RouteQuery {
+    recipient: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MailboxMessage {
+    from: String,
+    to: String,
+    subject: String,
+    html: String,
+    text: Option<String>,
+    codes: Vec<String>,
+    links: Vec<String>,
+    // Hashes some flows send along with the code, like the email verification reference
+    references: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteOutput {
+    messages: Vec<MailboxMessage>,
+}
+
+// Lets the frontend finish flows that need an email locally, newest messages come first
+#[axum::debug_handler]
+pub async fn mailbox(
+    Query(query): Query<RouteQuery>,
+    Extension(email_layer): Extension<EmailLayer>,
+) -> (StatusCode, Json<RouteOutput>) {
+    // 1. Retrieve the captured messages, optionally only the ones sent to a recipient
+
+    let recipient = query.recipient.as_deref().map(normalize_email);
+
+    let messages: Vec<MailboxMessage> = email_layer
+        .mailbox()
+        .map(|mailbox| mailbox.messages())
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .filter(|message| {
+            recipient
+                .as_ref()
+                .is_none_or(|recipient| normalize_email(&message.to) == *recipient)
+        })
+        .map(mailbox_message)
+        .collect();
+    println!("1. Mailbox messages retrieved successfully!");
+
+    (StatusCode::OK, Json(RouteOutput { messages }))
+}
+
+// Codes are written on a line of their own in the plain text part, the links are taken from
+// there as well so the html markup doesn't produce duplicates
+fn mailbox_message(message: EmailMessage) -> MailboxMessage {
+    let text = message.text.clone().unwrap_or_default();
+
+    let codes = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| (4..=10).contains(&line.len()) && line.chars().all(|c| c.is_ascii_digit()))
+        .map(String::from)
+        .collect();
+
+    let mut links: Vec<String> = vec![];
+    for link in LINK_REGEX.find_iter(&text) {
+        let link = link.as_str().trim_end_matches(['.', ',', ')']).to_string();
+
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+
+    let mut references: Vec<String> = vec![];
+    for reference in REFERENCE_REGEX.find_iter(&text) {
+        let reference = reference.as_str().to_string();
+
+        if !links.iter().any(|link| link.contains(&reference)) && !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+
+    MailboxMessage {
+        from: message.from,
+        to: message.to,
+        subject: message.subject,
+        html: message.html,
+        text: message.text,
+        codes,
+        links,
+        references,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_codes_links_and_references() {
+        let reference = "ab".repeat(32);
+        let token = "cd".repeat(32);
+        let text = format!(
+            "Use the code below.\n\n123456\n\nVerification reference: {}\n\nhttps://orvane.test/auth/magic-link/{}.\n",
+            reference, token
+        );
+
+        let message = mailbox_message(EmailMessage {
+            from: String::from("Orvane <noreply@orvane.test>"),
+            to: String::from("jane@example.com"),
+            subject: String::from("Orvane - Account Activation"),
+            html: String::new(),
+            text: Some(text),
+            idempotency_key: None,
+        });
+
+        assert_eq!(message.codes, vec!["123456"]);
+        assert_eq!(
+            message.links,
+            vec![format!("https://orvane.test/auth/magic-link/{}", token)]
+        );
+        assert_eq!(message.references, vec![reference]);
+    }
+}
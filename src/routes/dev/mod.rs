@@ -0,0 +1,12 @@
+pub mod mailbox;
+
+use axum::{routing::get, Router};
+
+pub use mailbox::mailbox;
+
+use crate::setup::AppState;
+
+// Only mounted while emails are captured in memory, see `setup_email_service`
+pub fn dev_router() -> Router<AppState> {
+    Router::new().route("/mailbox", get(mailbox))
+}
@@ -1,14 +1,24 @@
+pub mod account;
 pub mod auth;
+pub mod dev;
 
 use axum::Router;
 
 use crate::setup::AppState;
 
 fn api_v1_router() -> Router<AppState> {
-    Router::new().nest("/auth", auth::auth_router())
+    Router::new()
+        .nest("/auth", auth::auth_router())
+        .nest("/account", account::account_router())
 }
 
 // Main router that serves as the entry point for all routes
-pub fn main_router() -> Router<AppState> {
-    Router::new().nest("/api/v1", api_v1_router())
+pub fn main_router(dev_mailbox: bool) -> Router<AppState> {
+    let router = Router::new().nest("/api/v1", api_v1_router());
+
+    if dev_mailbox {
+        return router.nest("/dev", dev::dev_router());
+    }
+
+    router
 }
@@ -0,0 +1,262 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use derive_more::Display;
+use futures_util::future::BoxFuture;
+use sha1::{Digest, Sha1};
+
+#[derive(Debug, Display)]
+pub enum BreachCheckError {
+    Io(std::io::Error),
+    Http(reqwest::Error),
+}
+
+// Looks passwords up in a Have I Been Pwned style dataset, only the first 5 characters of the
+// SHA-1 hash select the range so the password itself never leaves the checker
+pub trait BreachedPasswordChecker: Send + Sync {
+    // Number of times the password appeared in known breaches, 0 if it was never seen
+    fn breach_count<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<u64, BreachCheckError>>;
+}
+
+// Directory of range files named by the hash prefix (`21BD1.txt`), each line being
+// `SUFFIX:COUNT` like the responses of the range API
+pub struct RangeDirectoryChecker {
+    path: PathBuf,
+}
+
+impl RangeDirectoryChecker {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl BreachedPasswordChecker for RangeDirectoryChecker {
+    fn breach_count<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<u64, BreachCheckError>> {
+        Box::pin(async move {
+            let (prefix, suffix) = hash_range(password);
+
+            let range = tokio::fs::read_to_string(self.path.join(format!("{}.txt", prefix)))
+                .await
+                .map_err(BreachCheckError::Io)?;
+
+            Ok(find_suffix_count(&range, &suffix))
+        })
+    }
+}
+
+// Failed checks let the password through, so a slow range API shouldn't hold up the request
+const RANGE_API_TIMEOUT: Duration = Duration::from_secs(3);
+
+pub struct RangeApiChecker {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl RangeApiChecker {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(RANGE_API_TIMEOUT)
+                .build()
+                .expect("Range API client couldn't be set up"),
+            base_url,
+        }
+    }
+}
+
+impl BreachedPasswordChecker for RangeApiChecker {
+    fn breach_count<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> BoxFuture<'a, Result<u64, BreachCheckError>> {
+        Box::pin(async move {
+            let (prefix, suffix) = hash_range(password);
+
+            // Padding hides the real size of the range from anyone watching the traffic
+            let range = self
+                .client
+                .get(format!("{}/range/{}", self.base_url, prefix))
+                .header("Add-Padding", "true")
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(BreachCheckError::Http)?
+                .text()
+                .await
+                .map_err(BreachCheckError::Http)?;
+
+            Ok(find_suffix_count(&range, &suffix))
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BreachedPasswordMode {
+    Off,
+    // Breached passwords are accepted, but the response tells the user to change it
+    Warn,
+    Reject,
+}
+
+#[derive(Clone)]
+pub struct BreachedPasswordScreening {
+    pub mode: BreachedPasswordMode,
+    checker: Option<Arc<dyn BreachedPasswordChecker>>,
+}
+
+impl BreachedPasswordScreening {
+    pub fn new(mode: BreachedPasswordMode, checker: Arc<dyn BreachedPasswordChecker>) -> Self {
+        Self {
+            mode,
+            checker: Some(checker),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            mode: BreachedPasswordMode::Off,
+            checker: None,
+        }
+    }
+
+    // Failed lookups are logged and treated as not breached so an unavailable dataset doesn't
+    // block signups
+    pub async fn is_breached(&self, password: &str) -> bool {
+        let checker = match (&self.checker, self.mode) {
+            (Some(checker), BreachedPasswordMode::Warn | BreachedPasswordMode::Reject) => checker,
+            _ => return false,
+        };
+
+        match checker.breach_count(password).await {
+            Ok(count) => count > 0,
+            Err(err) => {
+                println!("Breached password check failed: {}", err);
+                false
+            }
+        }
+    }
+}
+
+fn hash_range(password: &str) -> (String, String) {
+    let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = hash.split_at(5);
+
+    (prefix.to_string(), suffix.to_string())
+}
+
+fn find_suffix_count(range: &str, suffix: &str) -> u64 {
+    range
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(line_suffix, _)| line_suffix.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SHA-1 of "password" is 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8
+    const PASSWORD_SUFFIX: &str = "1E4C9B93F3F0682250B6CF8331B7EE68FD8";
+
+    struct StubChecker(Result<u64, ()>);
+
+    impl BreachedPasswordChecker for StubChecker {
+        fn breach_count<'a>(
+            &'a self,
+            _password: &'a str,
+        ) -> BoxFuture<'a, Result<u64, BreachCheckError>> {
+            let result = self
+                .0
+                .map_err(|_| BreachCheckError::Io(std::io::Error::other("range file missing")));
+
+            Box::pin(async move { result })
+        }
+    }
+
+    fn screening(mode: BreachedPasswordMode, result: Result<u64, ()>) -> BreachedPasswordScreening {
+        BreachedPasswordScreening::new(mode, Arc::new(StubChecker(result)))
+    }
+
+    #[test]
+    fn hash_range_splits_uppercase_sha1() {
+        let (prefix, suffix) = hash_range("password");
+
+        assert_eq!(prefix, "5BAA6");
+        assert_eq!(suffix, PASSWORD_SUFFIX);
+    }
+
+    #[test]
+    fn find_suffix_count_matches_case_insensitively() {
+        let range = format!(
+            "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n{}:3861493\r\n",
+            PASSWORD_SUFFIX.to_lowercase()
+        );
+
+        assert_eq!(find_suffix_count(&range, PASSWORD_SUFFIX), 3861493);
+    }
+
+    #[test]
+    fn find_suffix_count_treats_padding_and_missing_suffixes_as_unseen() {
+        let range = format!(
+            "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n{}:0\r\n",
+            PASSWORD_SUFFIX
+        );
+
+        assert_eq!(find_suffix_count(&range, PASSWORD_SUFFIX), 0);
+        assert_eq!(find_suffix_count("", PASSWORD_SUFFIX), 0);
+        assert_eq!(find_suffix_count("not a range", PASSWORD_SUFFIX), 0);
+    }
+
+    #[tokio::test]
+    async fn off_mode_never_reports_breaches() {
+        assert!(
+            !screening(BreachedPasswordMode::Off, Ok(10))
+                .is_breached("password")
+                .await
+        );
+        assert!(
+            !BreachedPasswordScreening::disabled()
+                .is_breached("password")
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn warn_and_reject_modes_report_breaches() {
+        for mode in [BreachedPasswordMode::Warn, BreachedPasswordMode::Reject] {
+            assert!(screening(mode, Ok(10)).is_breached("password").await);
+            assert!(!screening(mode, Ok(0)).is_breached("password").await);
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_checks_let_the_password_through() {
+        assert!(
+            !screening(BreachedPasswordMode::Reject, Err(()))
+                .is_breached("password")
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn unresponsive_range_api_lets_the_password_through() {
+        // Accepts connections but never answers, so the request runs into the client timeout
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let screening = BreachedPasswordScreening::new(
+            BreachedPasswordMode::Reject,
+            Arc::new(RangeApiChecker::new(base_url)),
+        );
+
+        assert!(!screening.is_breached("password").await);
+        drop(listener);
+    }
+}
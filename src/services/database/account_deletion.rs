@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
+    Surreal,
+};
+use validator::Validate;
+
+use crate::utils::crypto::hash_token;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct AccountDeletion {
+    // The id is the hash of the token sent in the cancellation link
+    pub id: Thing,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub scheduled_at: Datetime,
+
+    pub user: Thing,
+}
+
+#[derive(Clone)]
+pub struct AccountDeletionQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> AccountDeletionQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> AccountDeletionQuery<'a> {
+    // Marks the user for deletion and revokes all of their sessions
+    pub async fn schedule(
+        &self,
+        cancel_token: &str,
+        user_id: Thing,
+        scheduled_at: DateTime<Utc>,
+    ) -> Result<(), surrealdb::Error> {
+        let account_deletion_id =
+            Thing::from(("account_deletion".to_string(), hash_token(cancel_token)));
+
+        let create_query = r#"
+            DELETE FROM account_deletion WHERE user = $user;
+
+            CREATE account_deletion CONTENT {
+                id: $id,
+                created_at: $created_at,
+                scheduled_at: $scheduled_at,
+                user: $user
+            };
+
+            UPDATE user SET deletion_scheduled_at = $scheduled_at WHERE id = $user;
+
+            DELETE FROM session WHERE user = $user;
+        "#;
+
+        self.db
+            .query(BeginStatement::default())
+            .query(create_query)
+            .query(CommitStatement::default())
+            .bind(("id", account_deletion_id))
+            .bind(("created_at", Datetime::from(Utc::now())))
+            .bind(("scheduled_at", Datetime::from(scheduled_at)))
+            .bind(("user", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Returns the user whose deletion got cancelled
+    pub async fn cancel(&self, cancel_token: &str) -> Result<Thing, surrealdb::Error> {
+        let account_deletion_id =
+            Thing::from(("account_deletion".to_string(), hash_token(cancel_token)));
+
+        // The user is only updated if the deletion still existed, both in the same transaction so
+        // the scheduled date can't outlive the deletion it belonged to
+        let query = r#"
+            LET $deleted = (
+                DELETE FROM account_deletion
+                WHERE id = $account_deletion_id
+                RETURN BEFORE
+            );
+
+            UPDATE user SET deletion_scheduled_at = NONE WHERE id IN $deleted.user;
+
+            RETURN $deleted;
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(BeginStatement::default())
+            .query(query)
+            .query(CommitStatement::default())
+            .bind(("account_deletion_id", account_deletion_id))
+            .await?;
+
+        let mut result: Vec<AccountDeletion> = response.take(2)?;
+
+        match result.pop() {
+            Some(account_deletion) => Ok(account_deletion.user),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Account deletion either doesn't exist or was already cancelled"),
+            ))),
+        }
+    }
+
+    pub async fn list_due(&self) -> Result<Vec<AccountDeletion>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM account_deletion
+            WHERE scheduled_at <= $now
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("now", Datetime::from(Utc::now())))
+            .await?;
+
+        let result: Vec<AccountDeletion> = response.take(0)?;
+
+        Ok(result)
+    }
+}
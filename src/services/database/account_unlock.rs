@@ -0,0 +1,100 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
+    Surreal,
+};
+use validator::Validate;
+
+use crate::utils::crypto::hash_token;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct AccountUnlock {
+    // The id is the hash of the token sent in the unlock link
+    pub id: Thing,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub expires_at: Datetime,
+
+    pub user: Thing,
+}
+
+#[derive(Clone)]
+pub struct AccountUnlockQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> AccountUnlockQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> AccountUnlockQuery<'a> {
+    pub async fn create(&self, token: &str, user_id: Thing) -> Result<(), surrealdb::Error> {
+        let account_unlock_id = Thing::from(("account_unlock".to_string(), hash_token(token)));
+
+        let now: DateTime<Utc> = Utc::now();
+        let expires: DateTime<Utc> = now + Duration::days(1);
+
+        let delete_query = r#"
+            DELETE FROM account_unlock
+            WHERE user = $user
+        "#;
+
+        let create_query = r#"
+            CREATE account_unlock CONTENT {
+                id: $id,
+                created_at: $created_at,
+                expires_at: $expires_at,
+                user: $user
+            }
+        "#;
+
+        self.db
+            .query(BeginStatement::default())
+            .query(delete_query)
+            .query(create_query)
+            .query(CommitStatement::default())
+            .bind(("id", account_unlock_id))
+            .bind(("created_at", Datetime::from(now)))
+            .bind(("expires_at", Datetime::from(expires)))
+            .bind(("user", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn consume(
+        &self,
+        account_unlock_id: Thing,
+    ) -> Result<AccountUnlock, surrealdb::Error> {
+        let query = r#"
+            DELETE FROM account_unlock
+            WHERE id = $account_unlock_id
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("account_unlock_id", account_unlock_id))
+            .await?;
+
+        let mut result: Vec<AccountUnlock> = response.take(0)?;
+
+        match result.pop() {
+            Some(account_unlock) => Ok(account_unlock),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Account unlock either doesn't exist or was already used"),
+            ))),
+        }
+    }
+}
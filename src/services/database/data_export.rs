@@ -0,0 +1,136 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
+    Surreal,
+};
+use validator::Validate;
+
+use crate::utils::crypto::hash_token;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct DataExport {
+    // The id is the hash of the token sent in the download link
+    pub id: Thing,
+    // Serialized JSON archive
+    pub archive: String,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub expires_at: Datetime,
+
+    pub user: Thing,
+}
+
+#[derive(Clone)]
+pub struct DataExportQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> DataExportQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> DataExportQuery<'a> {
+    // Stores the archive, replacing the previous export of the user. Nothing is stored if the
+    // previous export was created within the cooldown, the check and the insert share a
+    // transaction so concurrent requests can't both pass it
+    pub async fn create(
+        &self,
+        token: &str,
+        user_id: Thing,
+        archive: String,
+        cooldown: Duration,
+    ) -> Result<Option<DateTime<Utc>>, surrealdb::Error> {
+        let data_export_id = Thing::from(("data_export".to_string(), hash_token(token)));
+
+        let now: DateTime<Utc> = Utc::now();
+        let expires: DateTime<Utc> = now + Duration::days(2);
+
+        let query = r#"
+            LET $recent = (
+                SELECT VALUE id FROM data_export
+                WHERE user = $user AND created_at > $cooldown_start
+            );
+
+            IF array::len($recent) > 0 {
+                false
+            } ELSE {
+                DELETE FROM data_export WHERE user = $user;
+
+                CREATE data_export CONTENT {
+                    id: $id,
+                    archive: $archive,
+                    created_at: $created_at,
+                    expires_at: $expires_at,
+                    user: $user
+                };
+
+                true
+            };
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(BeginStatement::default())
+            .query(query)
+            .query(CommitStatement::default())
+            .bind(("id", data_export_id))
+            .bind(("archive", archive))
+            .bind(("created_at", Datetime::from(now)))
+            .bind(("cooldown_start", Datetime::from(now - cooldown)))
+            .bind(("expires_at", Datetime::from(expires)))
+            .bind(("user", user_id))
+            .await?;
+
+        let created: Option<bool> = response.take(1)?;
+
+        Ok(created.unwrap_or(false).then_some(expires))
+    }
+
+    pub async fn get(&self, token: &str) -> Result<DataExport, surrealdb::Error> {
+        let data_export_id = Thing::from(("data_export".to_string(), hash_token(token)));
+
+        let query = r#"
+            SELECT * FROM data_export
+            WHERE id = $data_export_id
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("data_export_id", data_export_id))
+            .await?;
+
+        let mut result: Vec<DataExport> = response.take(0)?;
+
+        match result.pop() {
+            Some(data_export) => Ok(data_export),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Data export either doesn't exist or was already removed"),
+            ))),
+        }
+    }
+
+    pub async fn remove_expired(&self) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM data_export
+            WHERE expires_at < $now
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("now", Datetime::from(Utc::now())))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{Datetime, Thing},
+    Surreal,
+};
+
+use crate::{services::email::EmailMessage, utils::crypto::generate_token};
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_SENT: &str = "sent";
+pub const STATUS_DEAD: &str = "dead";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutboxEmail {
+    // The random part of the id doubles as the idempotency key of the delivery
+    pub id: Thing,
+    pub sender: String,
+    // Cleared once the email is dead, emails queued after an account deletion aren't linked to a
+    // user and would otherwise keep the address around
+    pub recipient: String,
+    pub subject: String,
+    // Cleared once the email is sent or dead, only the metadata is kept around
+    pub html: String,
+    #[serde(default)]
+    pub text: Option<String>,
+
+    // One of `pending`, `sent` or `dead`, dead emails stay around for inspection
+    pub status: String,
+    #[serde(default)]
+    pub attempts: i64,
+    #[serde(default)]
+    pub last_error: Option<String>,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub next_attempt_at: Datetime,
+    #[serde(default)]
+    pub sent_at: Option<Datetime>,
+
+    #[serde(default)]
+    pub user: Option<Thing>,
+}
+
+impl OutboxEmail {
+    pub fn idempotency_key(&self) -> String {
+        self.id.id.to_raw()
+    }
+
+    pub fn message(&self) -> EmailMessage {
+        EmailMessage {
+            from: self.sender.clone(),
+            to: self.recipient.clone(),
+            subject: self.subject.clone(),
+            html: self.html.clone(),
+            text: self.text.clone(),
+            idempotency_key: Some(self.idempotency_key()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EmailOutboxQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> EmailOutboxQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> EmailOutboxQuery<'a> {
+    // Emails are linked to the user they're addressed to, so they're removed along with the account
+    pub async fn enqueue(&self, message: &EmailMessage) -> Result<(), surrealdb::Error> {
+        let email_id = Thing::from(("email_outbox".to_string(), generate_token()));
+        let now = Datetime::from(Utc::now());
+
+        let query = r#"
+            LET $user = (SELECT VALUE id FROM user WHERE email = $recipient LIMIT 1)[0];
+
+            CREATE ONLY email_outbox CONTENT {
+                id: $id,
+                sender: $sender,
+                recipient: $recipient,
+                subject: $subject,
+                html: $html,
+                text: $text,
+                status: $status,
+                attempts: 0,
+                created_at: $created_at,
+                next_attempt_at: $created_at,
+                user: $user
+            };
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("id", email_id))
+            .bind(("sender", message.from.clone()))
+            .bind(("recipient", message.to.clone()))
+            .bind(("subject", message.subject.clone()))
+            .bind(("html", message.html.clone()))
+            .bind(("text", message.text.clone()))
+            .bind(("status", STATUS_PENDING))
+            .bind(("created_at", now))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn list_due(&self) -> Result<Vec<Thing>, surrealdb::Error> {
+        let query = r#"
+            SELECT VALUE id FROM email_outbox
+            WHERE status = $status AND next_attempt_at <= $now
+            ORDER BY next_attempt_at
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("status", STATUS_PENDING))
+            .bind(("now", Datetime::from(Utc::now())))
+            .await?;
+
+        let result: Vec<Thing> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    // Pushes the next attempt of a due email past `lease_until` before returning it, so other
+    // workers skip it and an email whose worker died becomes due again afterwards. Nothing is
+    // returned when another worker claimed the email first
+    pub async fn claim(
+        &self,
+        email_id: Thing,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<OutboxEmail>, surrealdb::Error> {
+        let query = r#"
+            UPDATE email_outbox
+            SET next_attempt_at = $lease_until
+            WHERE id = $email_id AND status = $status AND next_attempt_at <= $now
+            RETURN AFTER
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("email_id", email_id))
+            .bind(("lease_until", Datetime::from(lease_until)))
+            .bind(("status", STATUS_PENDING))
+            .bind(("now", Datetime::from(Utc::now())))
+            .await?;
+
+        let result: Vec<OutboxEmail> = response.take(0)?;
+
+        Ok(result.into_iter().next())
+    }
+
+    pub async fn mark_sent(&self, email_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE email_outbox
+            SET status = $status,
+                attempts += 1,
+                last_error = NONE,
+                sent_at = $sent_at,
+                html = "",
+                text = NONE
+            WHERE id = $email_id
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("email_id", email_id))
+            .bind(("status", STATUS_SENT))
+            .bind(("sent_at", Datetime::from(Utc::now())))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Without a next attempt the email is dead lettered and won't be picked up again
+    pub async fn record_failure(
+        &self,
+        email_id: Thing,
+        error: String,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<(), surrealdb::Error> {
+        let (status, next_attempt_at) = match next_attempt_at {
+            Some(next_attempt_at) => (STATUS_PENDING, next_attempt_at),
+            None => (STATUS_DEAD, Utc::now()),
+        };
+
+        let query = r#"
+            UPDATE email_outbox
+            SET status = $status,
+                attempts += 1,
+                last_error = $error,
+                next_attempt_at = $next_attempt_at
+            WHERE id = $email_id;
+
+            IF $dead {
+                UPDATE email_outbox
+                SET recipient = "", html = "", text = NONE
+                WHERE id = $email_id
+            };
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("email_id", email_id))
+            .bind(("dead", status == STATUS_DEAD))
+            .bind(("status", status))
+            .bind(("error", error))
+            .bind(("next_attempt_at", Datetime::from(next_attempt_at)))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn list_for_user(
+        &self,
+        user_id: Thing,
+    ) -> Result<Vec<OutboxEmail>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM email_outbox
+            WHERE user = $user
+            ORDER BY created_at DESC
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let result: Vec<OutboxEmail> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    pub async fn remove_sent_before(&self, before: DateTime<Utc>) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM email_outbox
+            WHERE status = $status AND sent_at < $before
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("status", STATUS_SENT))
+            .bind(("before", Datetime::from(before)))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
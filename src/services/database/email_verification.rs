@@ -2,25 +2,39 @@ use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use surrealdb::{
     engine::remote::ws::Client,
-    sql::{Datetime, Thing},
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
     Surreal,
 };
 use validator::Validate;
 
-use crate::utils::crypto::generate_token;
+use crate::utils::crypto::{generate_token, hash_token};
+
+pub const PURPOSE_SIGNUP: &str = "signup";
+pub const PURPOSE_EMAIL_CHANGE: &str = "email_change";
 
 #[derive(Serialize, Deserialize, Validate, Debug, Clone)]
 pub struct EmailVerification {
     pub id: Thing,
     pub code: String,
+    #[serde(default)]
+    pub attempts: i64,
+    #[serde(default)]
+    pub purpose: String,
+    // Address the user switches to, only set for email changes
+    #[serde(default)]
+    pub new_email: Option<String>,
+    #[serde(default)]
+    pub cancel_token_hash: Option<String>,
 
     #[serde(default)]
     pub created_at: Datetime,
     #[serde(default)]
-    expires_at: Datetime,
+    pub expires_at: Datetime,
 
-    #[serde(rename = "user")]
-    user: Thing,
+    pub user: Thing,
 }
 
 impl EmailVerification {
@@ -34,6 +48,10 @@ impl EmailVerification {
         EmailVerification {
             id,
             code,
+            attempts: 0,
+            purpose: String::from(PURPOSE_SIGNUP),
+            new_email: None,
+            cancel_token_hash: None,
             created_at,
             expires_at,
             user: user_id,
@@ -41,6 +59,35 @@ impl EmailVerification {
     }
 }
 
+// A verification email send, undelivered ones are retried by the background task
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmailVerificationDispatch {
+    pub id: Thing,
+    #[serde(default)]
+    pub delivered: bool,
+    #[serde(default)]
+    pub attempts: i64,
+    #[serde(default)]
+    pub created_at: Datetime,
+
+    pub user: Thing,
+}
+
+// Outcome of `reserve_dispatch`, a reserved dispatch counts towards the limits right away
+#[derive(Debug)]
+pub enum DispatchReservation {
+    Reserved(Thing),
+    Cooldown,
+    LimitReached,
+}
+
+#[derive(Deserialize, Debug)]
+struct DispatchReservationResult {
+    status: String,
+    #[serde(default)]
+    id: Option<Thing>,
+}
+
 #[derive(Clone)]
 pub struct EmailVerificationQuery<'a> {
     db: &'a Surreal<Client>,
@@ -53,13 +100,50 @@ impl<'a> EmailVerificationQuery<'a> {
 }
 
 impl<'a> EmailVerificationQuery<'a> {
-    // TODO: This function needs better error exception handling, take a look at it in free time
-    // TODO: Check if providing email is necessary since we already provide user_id
     pub async fn create(
         &self,
         code: String,
-        email: String,
         user_id: Thing,
+    ) -> Result<EmailVerification, surrealdb::Error> {
+        self.insert(
+            code,
+            user_id,
+            PURPOSE_SIGNUP,
+            None,
+            None,
+            Duration::minutes(5),
+        )
+        .await
+    }
+
+    // The cancel token is sent to the current address so its owner can stop the change
+    pub async fn create_email_change(
+        &self,
+        code: String,
+        user_id: Thing,
+        new_email: String,
+        cancel_token: &str,
+    ) -> Result<EmailVerification, surrealdb::Error> {
+        self.insert(
+            code,
+            user_id,
+            PURPOSE_EMAIL_CHANGE,
+            Some(new_email),
+            Some(hash_token(cancel_token)),
+            Duration::minutes(30),
+        )
+        .await
+    }
+
+    // TODO: This function needs better error exception handling, take a look at it in free time
+    async fn insert(
+        &self,
+        code: String,
+        user_id: Thing,
+        purpose: &str,
+        new_email: Option<String>,
+        cancel_token_hash: Option<String>,
+        lifetime: Duration,
     ) -> Result<EmailVerification, surrealdb::Error> {
         let email_verification_id_str = generate_token();
         let email_verification_id = Thing::from((
@@ -68,40 +152,24 @@ impl<'a> EmailVerificationQuery<'a> {
         ));
 
         let now: DateTime<Utc> = Utc::now();
-        let expires: DateTime<Utc> = now + Duration::minutes(5);
+        let expires: DateTime<Utc> = now + lifetime;
 
         let created_at = Datetime::from(now);
         let expires_at = Datetime::from(expires);
 
-        let check_query = r#"
-            SELECT * FROM email_verification
-            WHERE email = $email
-        "#;
-
-        let mut check = self
-            .db
-            .query(check_query)
-            .bind(("email", email.clone()))
-            .await?;
-
-        let email_verifications: Vec<EmailVerification> = check.take(0)?;
-
-        if !email_verifications.is_empty() {
-            let delete_query = r#"
-                DELETE FROM email_verification
-                WHERE email = $email
-            "#;
-
-            self.db
-                .query(delete_query)
-                .bind(("email", email.clone()))
-                .await?;
-        }
+        // Only the latest code of a user is valid for each purpose, the old codes are removed in
+        // the same transaction so a failed create doesn't leave the user without one
+        let query = r#"
+            DELETE FROM email_verification
+            WHERE user = $user_id AND purpose = $purpose;
 
-        let create_query = r#"
             CREATE email_verification CONTENT {
                 id: $id,
                 code: $code,
+                attempts: 0,
+                purpose: $purpose,
+                new_email: $new_email,
+                cancel_token_hash: $cancel_token_hash,
                 created_at: $created_at,
                 expires_at: $expires_at,
                 user: $user_id
@@ -110,15 +178,20 @@ impl<'a> EmailVerificationQuery<'a> {
 
         let mut response: surrealdb::Response = self
             .db
-            .query(create_query)
+            .query(BeginStatement::default())
+            .query(query)
+            .query(CommitStatement::default())
             .bind(("id", email_verification_id.clone()))
             .bind(("code", code.clone()))
+            .bind(("purpose", purpose.to_string()))
+            .bind(("new_email", new_email))
+            .bind(("cancel_token_hash", cancel_token_hash))
             .bind(("expires_at", expires_at.clone()))
             .bind(("created_at", created_at.clone()))
             .bind(("user_id", user_id.clone()))
             .await?;
 
-        let created: Option<EmailVerification> = response.take(0)?;
+        let created: Option<EmailVerification> = response.take(1)?;
 
         match created {
             Some(email_verification) => Ok(email_verification),
@@ -130,14 +203,22 @@ impl<'a> EmailVerificationQuery<'a> {
         }
     }
 
-    pub async fn get(&self, user_id: Thing) -> Result<EmailVerification, surrealdb::Error> {
+    pub async fn get(
+        &self,
+        user_id: Thing,
+        purpose: &str,
+    ) -> Result<EmailVerification, surrealdb::Error> {
         let query = r#"
             SELECT * FROM email_verification
-            WHERE user.id = $id
+            WHERE user.id = $id AND purpose = $purpose
         "#;
 
-        let mut response: surrealdb::Response =
-            self.db.query(query).bind(("id", user_id.clone())).await?;
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("id", user_id.clone()))
+            .bind(("purpose", purpose.to_string()))
+            .await?;
 
         let mut result: Vec<Option<EmailVerification>> = response.take(0)?;
 
@@ -149,6 +230,71 @@ impl<'a> EmailVerificationQuery<'a> {
         }
     }
 
+    pub async fn list(&self, user_id: Thing) -> Result<Vec<EmailVerification>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM email_verification
+            WHERE user = $user
+            ORDER BY created_at DESC
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let result: Vec<EmailVerification> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    pub async fn get_by_cancel_token(
+        &self,
+        cancel_token: &str,
+    ) -> Result<EmailVerification, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM email_verification
+            WHERE cancel_token_hash = $cancel_token_hash
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("cancel_token_hash", hash_token(cancel_token)))
+            .await?;
+
+        let mut result: Vec<EmailVerification> = response.take(0)?;
+
+        match result.pop() {
+            Some(email_verification) => Ok(email_verification),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Email change either doesn't exist or was already completed"),
+            ))),
+        }
+    }
+
+    // Counts an attempt in the same update that checks the limit, so concurrent requests can't
+    // exceed it, returns false once the verification ran out of attempts
+    pub async fn consume_attempt(
+        &self,
+        email_verification_id: Thing,
+        max_attempts: i64,
+    ) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            UPDATE email_verification
+            SET attempts += 1
+            WHERE id = $email_verification_id AND attempts < $max_attempts
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("email_verification_id", email_verification_id))
+            .bind(("max_attempts", max_attempts))
+            .await?;
+
+        let result: Vec<EmailVerification> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
+
     pub async fn remove(&self, email_verification_id: Thing) -> Result<(), surrealdb::Error> {
         let query = r#"
             DELETE FROM email_verification
@@ -175,4 +321,224 @@ impl<'a> EmailVerificationQuery<'a> {
 
         Ok(())
     }
+
+    // A delivered email supersedes the failed sends of the user, so they are no longer retried
+    pub async fn record_dispatch(
+        &self,
+        user_id: Thing,
+        delivered: bool,
+    ) -> Result<(), surrealdb::Error> {
+        // Dispatches older than a day don't count towards any limit anymore
+        let query = r#"
+            DELETE FROM email_verification_dispatch
+            WHERE user = $user AND created_at < $created_at - 1d;
+
+            IF $delivered {
+                DELETE FROM email_verification_dispatch
+                WHERE user = $user AND delivered = false
+            };
+
+            CREATE email_verification_dispatch CONTENT {
+                delivered: $delivered,
+                attempts: 0,
+                created_at: $created_at,
+                user: $user
+            };
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("delivered", delivered))
+            .bind(("created_at", Datetime::from(Utc::now())))
+            .bind(("user", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Checks the cooldown and the daily limit and records the dispatch in one transaction, so
+    // concurrent resends can't all pass the check
+    pub async fn reserve_dispatch(
+        &self,
+        user_id: Thing,
+        cooldown: Duration,
+        daily_limit: usize,
+    ) -> Result<DispatchReservation, surrealdb::Error> {
+        let query = r#"
+            DELETE FROM email_verification_dispatch
+            WHERE user = $user AND created_at < $created_at - 1d;
+
+            LET $sent = (
+                SELECT VALUE created_at FROM email_verification_dispatch
+                WHERE user = $user AND created_at > $created_at - 1d AND delivered != false
+                ORDER BY created_at DESC
+            );
+
+            IF array::len($sent) >= $daily_limit {
+                { status: "limit_reached" }
+            } ELSE IF array::len($sent) > 0 AND $sent[0] > $cooldown_start {
+                { status: "cooldown" }
+            } ELSE {
+                {
+                    status: "reserved",
+                    id: (CREATE ONLY email_verification_dispatch CONTENT {
+                        delivered: true,
+                        attempts: 0,
+                        created_at: $created_at,
+                        user: $user
+                    }).id
+                }
+            };
+        "#;
+
+        let now = Utc::now();
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(BeginStatement::default())
+            .query(query)
+            .query(CommitStatement::default())
+            .bind(("user", user_id))
+            .bind(("created_at", Datetime::from(now)))
+            .bind(("cooldown_start", Datetime::from(now - cooldown)))
+            .bind(("daily_limit", daily_limit))
+            .await?;
+
+        let result: Option<DispatchReservationResult> = response.take(2)?;
+
+        match result {
+            Some(DispatchReservationResult {
+                status,
+                id: Some(id),
+            }) if status == "reserved" => Ok(DispatchReservation::Reserved(id)),
+            Some(DispatchReservationResult { status, .. }) if status == "cooldown" => {
+                Ok(DispatchReservation::Cooldown)
+            }
+            Some(DispatchReservationResult { status, .. }) if status == "limit_reached" => {
+                Ok(DispatchReservation::LimitReached)
+            }
+            _ => Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(String::from(
+                    "Failed to reserve email verification dispatch",
+                )),
+            )),
+        }
+    }
+
+    // Settles a reserved dispatch once the email was handed off, a failed send is left to the
+    // retry task and doesn't count towards the limits anymore
+    pub async fn settle_dispatch(
+        &self,
+        dispatch_id: Thing,
+        user_id: Thing,
+        delivered: bool,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            IF $delivered {
+                DELETE FROM email_verification_dispatch
+                WHERE user = $user AND delivered = false
+            } ELSE {
+                UPDATE email_verification_dispatch
+                SET delivered = false
+                WHERE id = $dispatch_id
+            };
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("dispatch_id", dispatch_id))
+            .bind(("user", user_id))
+            .bind(("delivered", delivered))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Failed sends of users who are still unverified and haven't used up their retries
+    pub async fn list_undelivered_dispatches(
+        &self,
+        max_attempts: i64,
+    ) -> Result<Vec<EmailVerificationDispatch>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM email_verification_dispatch
+            WHERE delivered = false
+                AND attempts < $max_attempts
+                AND user.email_verified = false
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("max_attempts", max_attempts))
+            .await?;
+
+        let result: Vec<EmailVerificationDispatch> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    // Failed sends of a user are retried together, so a failed retry counts against all of them
+    pub async fn increment_dispatch_attempts(
+        &self,
+        user_id: Thing,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE email_verification_dispatch
+            SET attempts += 1
+            WHERE user = $user AND delivered = false
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("user", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn remove_dispatches_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM email_verification_dispatch
+            WHERE created_at < $before
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("before", Datetime::from(before)))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Times verification emails were sent to the user since the provided moment, newest first,
+    // failed sends don't count towards the resend limits
+    pub async fn dispatches_since(
+        &self,
+        user_id: Thing,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Datetime>, surrealdb::Error> {
+        let query = r#"
+            SELECT VALUE created_at FROM email_verification_dispatch
+            WHERE user = $user AND created_at > $since AND delivered != false
+            ORDER BY created_at DESC
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user", user_id))
+            .bind(("since", Datetime::from(since)))
+            .await?;
+
+        let result: Vec<Datetime> = response.take(0)?;
+
+        Ok(result)
+    }
 }
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{Datetime, Thing},
+    Surreal,
+};
+use validator::Validate;
+
+use crate::utils::validation::normalize_email;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct FailedSignin {
+    pub id: Thing,
+    pub email: String,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+
+    #[serde(default)]
+    pub user: Option<Thing>,
+}
+
+#[derive(Clone)]
+pub struct FailedSigninQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> FailedSigninQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> FailedSigninQuery<'a> {
+    pub async fn create(
+        &self,
+        email: String,
+        ip_address: Option<String>,
+        user_id: Option<Thing>,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            CREATE failed_signin CONTENT {
+                email: $email,
+                ip_address: $ip_address,
+                created_at: $created_at,
+                user: $user
+            }
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("email", normalize_email(&email)))
+            .bind(("ip_address", ip_address))
+            .bind(("created_at", Datetime::from(Utc::now())))
+            .bind(("user", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn count_for_user(
+        &self,
+        user_id: Thing,
+        since: DateTime<Utc>,
+    ) -> Result<usize, surrealdb::Error> {
+        let query = r#"
+            SELECT VALUE id FROM failed_signin
+            WHERE user = $user AND created_at > $since
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user", user_id))
+            .bind(("since", Datetime::from(since)))
+            .await?;
+
+        let result: Vec<Thing> = response.take(0)?;
+
+        Ok(result.len())
+    }
+
+    pub async fn count_for_ip_address(
+        &self,
+        ip_address: String,
+        since: DateTime<Utc>,
+    ) -> Result<usize, surrealdb::Error> {
+        let query = r#"
+            SELECT VALUE id FROM failed_signin
+            WHERE ip_address = $ip_address AND created_at > $since
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("ip_address", ip_address))
+            .bind(("since", Datetime::from(since)))
+            .await?;
+
+        let result: Vec<Thing> = response.take(0)?;
+
+        Ok(result.len())
+    }
+
+    pub async fn list_for_user(
+        &self,
+        user_id: Thing,
+    ) -> Result<Vec<FailedSignin>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM failed_signin
+            WHERE user = $user
+            ORDER BY created_at DESC
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let result: Vec<FailedSignin> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    pub async fn clear_for_user(&self, user_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM failed_signin
+            WHERE user = $user
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("user", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn remove_before(&self, before: DateTime<Utc>) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM failed_signin
+            WHERE created_at < $before
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("before", Datetime::from(before)))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
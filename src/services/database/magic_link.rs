@@ -0,0 +1,198 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
+    Surreal,
+};
+use validator::Validate;
+
+use crate::utils::crypto::hash_token;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct MagicLink {
+    // The id is the hash of the token sent in the link, the token itself is never stored
+    pub id: Thing,
+    pub code: String,
+    #[serde(default)]
+    pub attempts: i64,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub expires_at: Datetime,
+
+    pub user: Thing,
+}
+
+#[derive(Clone)]
+pub struct MagicLinkQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> MagicLinkQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> MagicLinkQuery<'a> {
+    // Replaces any previous magic link of the user, every link is recorded as a dispatch for the
+    // request limits
+    pub async fn create(
+        &self,
+        token: &str,
+        code: String,
+        user_id: Thing,
+    ) -> Result<MagicLink, surrealdb::Error> {
+        let magic_link_id = Thing::from(("magic_link".to_string(), hash_token(token)));
+
+        let now: DateTime<Utc> = Utc::now();
+        let expires: DateTime<Utc> = now + Duration::minutes(10);
+
+        let delete_query = r#"
+            DELETE FROM magic_link
+            WHERE user = $user
+        "#;
+
+        let create_query = r#"
+            CREATE magic_link CONTENT {
+                id: $id,
+                code: $code,
+                attempts: 0,
+                created_at: $created_at,
+                expires_at: $expires_at,
+                user: $user
+            }
+        "#;
+
+        // Dispatches older than a day don't count towards any limit anymore
+        let dispatch_query = r#"
+            DELETE FROM magic_link_dispatch
+            WHERE user = $user AND created_at < $created_at - 1d;
+
+            CREATE magic_link_dispatch CONTENT {
+                created_at: $created_at,
+                user: $user
+            };
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(BeginStatement::default())
+            .query(delete_query)
+            .query(create_query)
+            .query(dispatch_query)
+            .query(CommitStatement::default())
+            .bind(("id", magic_link_id))
+            .bind(("code", code))
+            .bind(("created_at", Datetime::from(now)))
+            .bind(("expires_at", Datetime::from(expires)))
+            .bind(("user", user_id))
+            .await?;
+
+        let created: Option<MagicLink> = response.take(1)?;
+
+        match created {
+            Some(magic_link) => Ok(magic_link),
+            None => Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest("Failed to create magic link".to_string()),
+            )),
+        }
+    }
+
+    pub async fn get(&self, user_id: Thing) -> Result<MagicLink, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM magic_link
+            WHERE user = $user
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let mut result: Vec<Option<MagicLink>> = response.take(0)?;
+
+        match result.pop().flatten() {
+            Some(magic_link) => Ok(magic_link),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Magic link for provided user couldn't be found"),
+            ))),
+        }
+    }
+
+    // Counts an attempt in the same update that checks the limit, so concurrent guesses can't
+    // exceed it, returns false once the link ran out of attempts
+    pub async fn consume_attempt(
+        &self,
+        magic_link_id: Thing,
+        max_attempts: i64,
+    ) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            UPDATE magic_link
+            SET attempts += 1
+            WHERE id = $magic_link_id AND attempts < $max_attempts
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("magic_link_id", magic_link_id))
+            .bind(("max_attempts", max_attempts))
+            .await?;
+
+        let result: Vec<MagicLink> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
+
+    // Times magic links were sent to the user since the provided moment, newest first
+    pub async fn dispatches_since(
+        &self,
+        user_id: Thing,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Datetime>, surrealdb::Error> {
+        let query = r#"
+            SELECT VALUE created_at FROM magic_link_dispatch
+            WHERE user = $user AND created_at > $since
+            ORDER BY created_at DESC
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user", user_id))
+            .bind(("since", Datetime::from(since)))
+            .await?;
+
+        let result: Vec<Datetime> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    // Deleting the record is what makes the link single use, only one request can get it back
+    pub async fn consume(&self, magic_link_id: Thing) -> Result<MagicLink, surrealdb::Error> {
+        let query = r#"
+            DELETE FROM magic_link
+            WHERE id = $magic_link_id
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("magic_link_id", magic_link_id))
+            .await?;
+
+        let mut result: Vec<MagicLink> = response.take(0)?;
+
+        match result.pop() {
+            Some(magic_link) => Ok(magic_link),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Magic link either doesn't exist or was already used"),
+            ))),
+        }
+    }
+}
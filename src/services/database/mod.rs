@@ -1,7 +1,16 @@
+pub mod account_deletion;
+pub mod account_unlock;
+pub mod data_export;
+pub mod email_outbox;
 pub mod email_verification;
+pub mod failed_signin;
+pub mod magic_link;
+pub mod passkey;
 pub mod password_reset_request;
+pub mod recovery_code;
 pub mod session;
 pub mod user;
+pub mod webauthn_challenge;
 
 use std::task::{Context, Poll};
 
@@ -14,14 +23,37 @@ use surrealdb::{
 };
 use tower::{Layer, Service};
 
+// Unique index violations only arrive as a query error message from the remote engine
+pub fn is_unique_violation(error: &surrealdb::Error, index: &str) -> bool {
+    match error {
+        surrealdb::Error::Db(surrealdb::error::Db::IndexExists {
+            index: violated_index,
+            ..
+        }) => violated_index == index,
+        surrealdb::Error::Api(surrealdb::error::Api::Query(message)) => {
+            message.contains(&format!("Database index `{}` already contains", index))
+        }
+        _ => false,
+    }
+}
+
 #[derive(Clone)]
 pub struct DatabaseQuery<'a> {
     #[allow(dead_code)]
     db: &'a Surreal<Client>,
     pub user: user::UserQuery<'a>,
+    pub account_deletion: account_deletion::AccountDeletionQuery<'a>,
+    pub account_unlock: account_unlock::AccountUnlockQuery<'a>,
+    pub data_export: data_export::DataExportQuery<'a>,
+    pub email_outbox: email_outbox::EmailOutboxQuery<'a>,
     pub email_verification: email_verification::EmailVerificationQuery<'a>,
+    pub failed_signin: failed_signin::FailedSigninQuery<'a>,
+    pub magic_link: magic_link::MagicLinkQuery<'a>,
+    pub passkey: passkey::PasskeyQuery<'a>,
     pub password_reset_request: password_reset_request::PasswordResetRequestQuery<'a>,
+    pub recovery_code: recovery_code::RecoveryCodeQuery<'a>,
     pub session: session::SessionQuery<'a>,
+    pub webauthn_challenge: webauthn_challenge::WebauthnChallengeQuery<'a>,
 }
 
 #[allow(dead_code)]
@@ -67,7 +99,7 @@ impl DatabaseLayer {
 
     pub async fn initialize_schemas(&self, schemas: Vec<&str>) -> Result<(), surrealdb::Error> {
         for schema_query in schemas {
-            self.db.query(schema_query).await?;
+            self.db.query(schema_query).await?.check()?;
         }
 
         Ok(())
@@ -77,11 +109,20 @@ impl DatabaseLayer {
         DatabaseQuery {
             db: &self.db,
             user: user::UserQuery::new(&self.db),
+            account_deletion: account_deletion::AccountDeletionQuery::new(&self.db),
+            account_unlock: account_unlock::AccountUnlockQuery::new(&self.db),
+            data_export: data_export::DataExportQuery::new(&self.db),
+            email_outbox: email_outbox::EmailOutboxQuery::new(&self.db),
             email_verification: email_verification::EmailVerificationQuery::new(&self.db),
+            failed_signin: failed_signin::FailedSigninQuery::new(&self.db),
+            magic_link: magic_link::MagicLinkQuery::new(&self.db),
+            passkey: passkey::PasskeyQuery::new(&self.db),
             password_reset_request: password_reset_request::PasswordResetRequestQuery::new(
                 &self.db,
             ),
+            recovery_code: recovery_code::RecoveryCodeQuery::new(&self.db),
             session: session::SessionQuery::new(&self.db),
+            webauthn_challenge: webauthn_challenge::WebauthnChallengeQuery::new(&self.db),
         }
     }
 }
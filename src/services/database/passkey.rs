@@ -0,0 +1,142 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{Datetime, Thing},
+    Surreal,
+};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct Passkey {
+    pub id: Thing,
+    // Base64url encoded credential id, as sent by the browser
+    pub credential_id: String,
+    // Hex encoded uncompressed SEC1 P-256 public key
+    pub public_key: String,
+    pub sign_count: i64,
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub last_used_at: Option<Datetime>,
+
+    pub user: Thing,
+}
+
+#[derive(Clone)]
+pub struct PasskeyQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> PasskeyQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> PasskeyQuery<'a> {
+    pub async fn create(
+        &self,
+        user_id: Thing,
+        credential_id: String,
+        public_key: String,
+        sign_count: i64,
+        name: Option<String>,
+    ) -> Result<Passkey, surrealdb::Error> {
+        let created_at = Datetime::from(Utc::now());
+
+        let query = r#"
+            CREATE passkey CONTENT {
+                credential_id: $credential_id,
+                public_key: $public_key,
+                sign_count: $sign_count,
+                name: $name,
+                created_at: $created_at,
+                user: $user
+            }
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("credential_id", credential_id))
+            .bind(("public_key", public_key))
+            .bind(("sign_count", sign_count))
+            .bind(("name", name))
+            .bind(("created_at", created_at))
+            .bind(("user", user_id))
+            .await?;
+
+        let created: Option<Passkey> = response.take(0)?;
+
+        match created {
+            Some(passkey) => Ok(passkey),
+            None => Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest("Failed to create passkey".to_string()),
+            )),
+        }
+    }
+
+    pub async fn get_by_credential_id(
+        &self,
+        credential_id: String,
+    ) -> Result<Passkey, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM passkey
+            WHERE credential_id = $credential_id
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("credential_id", credential_id))
+            .await?;
+
+        let mut result: Vec<Option<Passkey>> = response.take(0)?;
+
+        match result.pop().flatten() {
+            Some(passkey) => Ok(passkey),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Passkey with provided credential id couldn't be found"),
+            ))),
+        }
+    }
+
+    pub async fn list(&self, user_id: Thing) -> Result<Vec<Passkey>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM passkey
+            WHERE user = $user
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let result: Vec<Passkey> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    pub async fn update_usage(
+        &self,
+        passkey_id: Thing,
+        sign_count: i64,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE passkey
+            SET sign_count = $sign_count, last_used_at = $last_used_at
+            WHERE id = $passkey_id
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("passkey_id", passkey_id))
+            .bind(("sign_count", sign_count))
+            .bind(("last_used_at", Datetime::from(Utc::now())))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -21,7 +21,7 @@ pub struct PasswordResetRequest {
     #[serde(default)]
     pub expires_at: Datetime,
 
-    user: Thing,
+    pub user: Thing,
 }
 
 impl PasswordResetRequest {
@@ -97,4 +97,48 @@ impl<'a> PasswordResetRequestQuery<'a> {
             )),
         }
     }
+
+    pub async fn get(&self, user_id: Thing) -> Result<PasswordResetRequest, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM password_reset_request
+            WHERE user.id = $id
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("id", user_id.clone())).await?;
+
+        let mut result: Vec<Option<PasswordResetRequest>> = response.take(0)?;
+
+        match result.pop().flatten() {
+            Some(password_reset_request) => Ok(password_reset_request),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Password reset request for provided user couldn't be found"),
+            ))),
+        }
+    }
+
+    // Removes the request only while it still belongs to the user, returns whether this call
+    // removed it so a token can't be used by two concurrent requests
+    pub async fn consume(
+        &self,
+        password_reset_request_id: Thing,
+        user_id: Thing,
+    ) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            DELETE FROM password_reset_request
+            WHERE id = $password_reset_request_id AND user = $user
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("password_reset_request_id", password_reset_request_id))
+            .bind(("user", user_id))
+            .await?;
+
+        let result: Vec<PasswordResetRequest> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
 }
@@ -0,0 +1,107 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
+    Surreal,
+};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct RecoveryCode {
+    pub id: Thing,
+    // Argon2 hash, recovery codes are never stored in plaintext
+    pub code_hash: String,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+
+    pub user: Thing,
+}
+
+#[derive(Clone)]
+pub struct RecoveryCodeQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> RecoveryCodeQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> RecoveryCodeQuery<'a> {
+    // Replaces all recovery codes of the user with the provided hashes
+    pub async fn replace_all(
+        &self,
+        user_id: Thing,
+        code_hashes: Vec<String>,
+    ) -> Result<(), surrealdb::Error> {
+        let created_at = Datetime::from(Utc::now());
+
+        let delete_query = r#"
+            DELETE FROM recovery_code
+            WHERE user = $user
+        "#;
+
+        let create_query = r#"
+            FOR $code_hash IN $code_hashes {
+                CREATE recovery_code CONTENT {
+                    code_hash: $code_hash,
+                    created_at: $created_at,
+                    user: $user
+                };
+            }
+        "#;
+
+        self.db
+            .query(BeginStatement::default())
+            .query(delete_query)
+            .query(create_query)
+            .query(CommitStatement::default())
+            .bind(("user", user_id))
+            .bind(("code_hashes", code_hashes))
+            .bind(("created_at", created_at))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    pub async fn list(&self, user_id: Thing) -> Result<Vec<RecoveryCode>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM recovery_code
+            WHERE user = $user
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let result: Vec<RecoveryCode> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    // Deletes the recovery code and reports whether this call was the one that consumed it, so
+    // the same code can't be used by two concurrent requests
+    pub async fn consume(&self, recovery_code_id: Thing) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            DELETE FROM recovery_code
+            WHERE id = $recovery_code_id
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("recovery_code_id", recovery_code_id))
+            .await?;
+
+        let result: Vec<RecoveryCode> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
+}
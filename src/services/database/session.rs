@@ -21,9 +21,27 @@ pub struct Session {
     #[serde(default)]
     pub last_accessed_at: Datetime,
 
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub device_label: Option<String>,
+
+    // Second factor codes tried on this pending session
+    #[serde(default)]
+    pub second_factor_attempts: i64,
+
     pub user: Thing,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetadata {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub device_label: String,
+}
+
 #[derive(Clone)]
 pub struct SessionQuery<'a> {
     db: &'a Surreal<Client>,
@@ -37,11 +55,14 @@ impl<'a> SessionQuery<'a> {
 
 impl<'a> SessionQuery<'a> {
     // TODO: Split into two functions (create_unauthorized) to avoid booleans as an argument
+    // Returns the created session alongside the raw token, only the token hash is stored in the
+    // database so the token has to be handed over to the client right away
     pub async fn create(
         &self,
         user_id: Thing,
         authorized: bool,
-    ) -> Result<Session, surrealdb::Error> {
+        metadata: SessionMetadata,
+    ) -> Result<(Session, String), surrealdb::Error> {
         let token = generate_token();
 
         let session_id_str = hash_token(&token);
@@ -60,6 +81,9 @@ impl<'a> SessionQuery<'a> {
                 created_at: $created_at,
                 expires_at: $expires_at,
                 last_accessed_at: $last_accessed_at,
+                user_agent: $user_agent,
+                ip_address: $ip_address,
+                device_label: $device_label,
                 user: $user
             }
         "#;
@@ -68,17 +92,20 @@ impl<'a> SessionQuery<'a> {
             .db
             .query(query)
             .bind(("id", session_id.clone()))
-            .bind(("authorized", authorized.clone()))
+            .bind(("authorized", authorized))
             .bind(("created_at", created_at.clone()))
             .bind(("expires_at", expires_at.clone()))
             .bind(("last_accessed_at", created_at.clone()))
+            .bind(("user_agent", metadata.user_agent))
+            .bind(("ip_address", metadata.ip_address))
+            .bind(("device_label", metadata.device_label))
             .bind(("user", user_id.clone()))
             .await?;
 
         let created: Option<Session> = response.take(0)?;
 
         match created {
-            Some(session) => Ok(session),
+            Some(session) => Ok((session, token)),
             None => Err(surrealdb::Error::Api(
                 surrealdb::error::Api::InvalidRequest("Failed to create session".to_string()),
             )),
@@ -94,15 +121,93 @@ impl<'a> SessionQuery<'a> {
         // })
     }
 
-    pub async fn invalidate_all(&self, user_id: Thing) -> Result<(), surrealdb::Error> {
+    pub async fn get(&self, session_id: Thing) -> Result<Session, surrealdb::Error> {
         let query = r#"
-            DELETE session 
-            WHERE user = $id
-            RETURN BEFORE;
+            SELECT * FROM session
+            WHERE id = $id
         "#;
 
         let mut response: surrealdb::Response =
-            self.db.query(query).bind(("id", user_id.clone())).await?;
+            self.db.query(query).bind(("id", session_id)).await?;
+
+        let mut result: Vec<Option<Session>> = response.take(0)?;
+
+        match result.pop().flatten() {
+            Some(session) => Ok(session),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Session with provided id couldn't be found"),
+            ))),
+        }
+    }
+
+    pub async fn list(&self, user_id: Thing) -> Result<Vec<Session>, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM session
+            WHERE user = $user AND expires_at > time::now()
+            ORDER BY last_accessed_at DESC
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user", user_id)).await?;
+
+        let result: Vec<Session> = response.take(0)?;
+
+        Ok(result)
+    }
+
+    pub async fn touch(&self, session_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE session
+            SET last_accessed_at = $last_accessed_at
+            WHERE id = $id
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("id", session_id))
+            .bind(("last_accessed_at", Datetime::from(Utc::now())))
+            .await?;
+
+        Ok(())
+    }
+
+    // Counts an attempt in the same update that checks the limit, so concurrent requests can't
+    // exceed it, returns false once the session ran out of attempts
+    pub async fn consume_second_factor_attempt(
+        &self,
+        session_id: Thing,
+        max_attempts: i64,
+    ) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            UPDATE session
+            SET second_factor_attempts += 1
+            WHERE id = $session_id AND second_factor_attempts < $max_attempts
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("session_id", session_id))
+            .bind(("max_attempts", max_attempts))
+            .await?;
+
+        let result: Vec<Session> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
+
+    pub async fn remove(&self, session_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM session
+            WHERE id = $session_id
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("session_id", session_id))
+            .await?;
 
         let result: Vec<Session> = response.take(0)?;
 
@@ -110,11 +215,89 @@ impl<'a> SessionQuery<'a> {
         if result.is_empty() {
             return Err(surrealdb::Error::Api(
                 surrealdb::error::Api::InvalidRequest(String::from(
-                    "No session found for the specified user, or the user does not exist.",
+                    "Session either doesn't exist or is already deleted",
                 )),
             ));
         }
 
         Ok(())
     }
+
+    // Same as `remove`, but only succeeds when the session belongs to the provided user
+    pub async fn remove_for_user(
+        &self,
+        session_id: Thing,
+        user_id: Thing,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM session
+            WHERE id = $session_id AND user = $user
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("session_id", session_id))
+            .bind(("user", user_id))
+            .await?;
+
+        let result: Vec<Session> = response.take(0)?;
+
+        if result.is_empty() {
+            return Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("Session with provided id couldn't be found"),
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub async fn invalidate_all(&self, user_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE session 
+            WHERE user = $id
+            RETURN BEFORE;
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("id", user_id.clone())).await?;
+
+        // A user without any active sessions is not an error, there is simply nothing to revoke
+        let _result: Vec<Session> = response.take(0)?;
+
+        Ok(())
+    }
+
+    // Restricted and pending second factor sessions, authorized ones stay signed in
+    pub async fn invalidate_unauthorized(&self, user_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE session
+            WHERE user = $id AND authorized = false
+        "#;
+
+        self.db.query(query).bind(("id", user_id)).await?.check()?;
+
+        Ok(())
+    }
+
+    pub async fn invalidate_all_except(
+        &self,
+        user_id: Thing,
+        session_id: Thing,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE session
+            WHERE user = $id AND id != $session_id
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("id", user_id))
+            .bind(("session_id", session_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
 }
@@ -1,13 +1,26 @@
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 use surrealdb::{
     engine::remote::ws::Client,
-    sql::{Datetime, Thing},
+    sql::{
+        statements::{BeginStatement, CommitStatement},
+        Datetime, Thing,
+    },
     Surreal,
 };
 use validator::Validate;
 
-use crate::utils::crypto::generate_token;
+use crate::{
+    services::database::{
+        email_verification::{EmailVerification, PURPOSE_SIGNUP},
+        session::{Session, SessionMetadata},
+    },
+    services::i18n::{current_locale, Locale},
+    utils::{
+        crypto::{generate_token, hash_token},
+        validation::normalize_email,
+    },
+};
 
 #[derive(Serialize, Deserialize, Validate, Debug, Clone)]
 pub struct User {
@@ -21,6 +34,30 @@ pub struct User {
     pub email_verified: bool,
     #[serde(default)]
     pub created_at: Datetime,
+
+    // Encrypted with the TOTP encryption key, never exposed to the client
+    #[serde(default)]
+    pub totp_secret: Option<String>,
+    #[serde(default)]
+    pub totp_enabled: bool,
+    // Time step of the last accepted TOTP code, older and equal steps are rejected as replays
+    #[serde(default)]
+    pub totp_last_step: Option<i64>,
+    // Codes tried against the pending TOTP secret, a new setup starts over
+    #[serde(default)]
+    pub totp_setup_attempts: i64,
+
+    #[serde(default)]
+    pub locked_until: Option<Datetime>,
+    #[serde(default)]
+    pub lock_count: i64,
+
+    #[serde(default)]
+    pub deletion_scheduled_at: Option<Datetime>,
+
+    // Language code of the locale picked by the user, see `Locale::from_code`
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 impl User {
@@ -31,8 +68,31 @@ impl User {
             password_hash,
             email_verified: false,
             created_at,
+            totp_secret: None,
+            totp_enabled: false,
+            totp_last_step: None,
+            totp_setup_attempts: 0,
+            locked_until: None,
+            lock_count: 0,
+            deletion_scheduled_at: None,
+            locale: None,
         }
     }
+
+    // Locale for emails sent without the user's session, falls back to the locale of the current
+    // request and to the default one outside of requests
+    pub fn preferred_locale(&self) -> Locale {
+        self.locale
+            .as_deref()
+            .and_then(Locale::from_code)
+            .unwrap_or_else(current_locale)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct NormalizedEmailGroup {
+    normalized_email: String,
+    users: Vec<Thing>,
 }
 
 #[derive(Clone)]
@@ -47,38 +107,92 @@ impl<'a> UserQuery<'a> {
 }
 
 impl<'a> UserQuery<'a> {
-    pub async fn create(
+    // Creates the user together with the signup verification and an unauthorized session, so a
+    // failure halfway doesn't leave an orphaned user behind. Returns the raw session token
+    // alongside the records, same as `SessionQuery::create`. Fails with a unique index violation
+    // on `user_email` if the email is taken, see `is_unique_violation`
+    pub async fn create_for_signup(
         &self,
         email: String,
         password_hash: String,
-    ) -> Result<User, surrealdb::Error> {
-        let user_id_str = generate_token();
-        let user_id = Thing::from(("user".to_string(), user_id_str.clone()));
+        verification_code: String,
+        locale: String,
+        metadata: SessionMetadata,
+    ) -> Result<(User, EmailVerification, Session, String), surrealdb::Error> {
+        let user_id = Thing::from(("user".to_string(), generate_token()));
+        let email_verification_id =
+            Thing::from(("email_verification".to_string(), generate_token()));
 
-        let created_at = Datetime::from(Utc::now());
+        let session_token = generate_token();
+        let session_id = Thing::from(("session".to_string(), hash_token(&session_token)));
+
+        let now = Utc::now();
+        let created_at = Datetime::from(now);
+        let verification_expires_at = Datetime::from(now + Duration::minutes(5));
+        let session_expires_at = Datetime::from(now + Duration::days(30));
 
         let query = r#"
-            CREATE type::thing("user", $id) SET
+            CREATE ONLY $user_id SET
                 email = $email,
                 email_verified = false,
                 password_hash = $password_hash,
-                created_at = $created_at
+                locale = $locale,
+                created_at = $created_at;
+
+            CREATE ONLY email_verification CONTENT {
+                id: $email_verification_id,
+                code: $code,
+                attempts: 0,
+                purpose: $purpose,
+                created_at: $created_at,
+                expires_at: $verification_expires_at,
+                user: $user_id
+            };
+
+            CREATE ONLY session CONTENT {
+                id: $session_id,
+                authorized: false,
+                created_at: $created_at,
+                expires_at: $session_expires_at,
+                last_accessed_at: $created_at,
+                user_agent: $user_agent,
+                ip_address: $ip_address,
+                device_label: $device_label,
+                user: $user_id
+            };
         "#;
 
         let mut response: surrealdb::Response = self
             .db
+            .query(BeginStatement::default())
             .query(query)
-            .bind(("id", user_id.clone()))
-            .bind(("email", email.clone()))
-            .bind(("password_hash", password_hash.clone()))
-            .bind(("created_at", created_at.clone()))
+            .query(CommitStatement::default())
+            .bind(("user_id", user_id))
+            .bind(("email", normalize_email(&email)))
+            .bind(("password_hash", password_hash))
+            .bind(("locale", locale))
+            .bind(("created_at", created_at))
+            .bind(("email_verification_id", email_verification_id))
+            .bind(("code", verification_code))
+            .bind(("purpose", PURPOSE_SIGNUP))
+            .bind(("verification_expires_at", verification_expires_at))
+            .bind(("session_id", session_id))
+            .bind(("session_expires_at", session_expires_at))
+            .bind(("user_agent", metadata.user_agent))
+            .bind(("ip_address", metadata.ip_address))
+            .bind(("device_label", metadata.device_label))
             .await?;
 
-        let created: Option<User> = response.take(0)?;
+        // The user statement goes first so a unique index violation surfaces as its own error
+        let user: Option<User> = response.take(0)?;
+        let email_verification: Option<EmailVerification> = response.take(1)?;
+        let session: Option<Session> = response.take(2)?;
 
-        match created {
-            Some(user) => Ok(user),
-            None => Err(surrealdb::Error::Api(
+        match (user, email_verification, session) {
+            (Some(user), Some(email_verification), Some(session)) => {
+                Ok((user, email_verification, session, session_token))
+            }
+            _ => Err(surrealdb::Error::Api(
                 surrealdb::error::Api::InvalidRequest("Failed to create user".to_string()),
             )),
         }
@@ -93,7 +207,7 @@ impl<'a> UserQuery<'a> {
         let mut response: surrealdb::Response = self
             .db
             .query(query)
-            .bind(("user_email", email.clone()))
+            .bind(("user_email", normalize_email(&email)))
             .await?;
 
         let mut result: Vec<Option<User>> = response.take(0)?;
@@ -106,6 +220,25 @@ impl<'a> UserQuery<'a> {
         }
     }
 
+    pub async fn get_by_id(&self, user_id: Thing) -> Result<User, surrealdb::Error> {
+        let query = r#"
+            SELECT * FROM user
+            WHERE id = $user_id
+        "#;
+
+        let mut response: surrealdb::Response =
+            self.db.query(query).bind(("user_id", user_id)).await?;
+
+        let mut result: Vec<Option<User>> = response.take(0)?;
+
+        match result.pop().flatten() {
+            Some(user) => Ok(user),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("User with provided id couldn't be found"),
+            ))),
+        }
+    }
+
     // TODO: Instead of using string referance use normal string and clone the input in the
     // implemenation
     pub async fn check_if_exists(&self, email: String) -> Result<bool, surrealdb::Error> {
@@ -117,7 +250,7 @@ impl<'a> UserQuery<'a> {
         let mut response: surrealdb::Response = self
             .db
             .query(query)
-            .bind(("user_email", email.clone()))
+            .bind(("user_email", normalize_email(&email)))
             .await?;
 
         let result: Vec<Option<User>> = response.take(0)?;
@@ -147,4 +280,327 @@ impl<'a> UserQuery<'a> {
 
         Ok(result)
     }
+
+    pub async fn update_password(
+        &self,
+        user_id: Thing,
+        password_hash: String,
+    ) -> Result<User, surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET password_hash = $password_hash
+            WHERE id = $user_id
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("password_hash", password_hash))
+            .await?;
+
+        let mut result: Vec<User> = response.take(0)?;
+
+        match result.pop() {
+            Some(user) => Ok(user),
+            None => Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(String::from("User doesn't exist")),
+            )),
+        }
+    }
+
+    // Permanently removes the user together with every record that references them, but only while
+    // the account deletion is still due. The check runs in the same transaction so a cancellation
+    // landing in between keeps the account, returns whether the user got deleted
+    pub async fn delete_with_related(&self, user_id: Thing) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            LET $email = (SELECT VALUE email FROM ONLY $user);
+            LET $due = array::len(
+                SELECT id FROM account_deletion
+                WHERE user = $user AND scheduled_at <= time::now()
+            ) > 0;
+
+            IF $due {
+                DELETE FROM session WHERE user = $user;
+                DELETE FROM email_verification WHERE user = $user;
+                DELETE FROM email_verification_dispatch WHERE user = $user;
+                DELETE FROM password_reset_request WHERE user = $user;
+                DELETE FROM recovery_code WHERE user = $user;
+                DELETE FROM passkey WHERE user = $user;
+                DELETE FROM webauthn_challenge WHERE user = $user;
+                DELETE FROM magic_link WHERE user = $user;
+                DELETE FROM magic_link_dispatch WHERE user = $user;
+                DELETE FROM failed_signin WHERE user = $user OR email = $email;
+                DELETE FROM account_unlock WHERE user = $user;
+                DELETE FROM account_deletion WHERE user = $user;
+                DELETE FROM data_export WHERE user = $user;
+                DELETE FROM email_outbox WHERE user = $user OR recipient = $email;
+                DELETE $user;
+            };
+
+            RETURN $due;
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(BeginStatement::default())
+            .query(query)
+            .query(CommitStatement::default())
+            .bind(("user", user_id))
+            .await?;
+
+        let deleted: Option<bool> = response.take(3)?;
+
+        Ok(deleted.unwrap_or(false))
+    }
+
+    // Lowercases and trims the emails stored before lookups were normalized. Addresses that
+    // only differ by case can't be merged automatically, so they're reported and nothing changes
+    // until they're resolved by hand
+    pub async fn normalize_emails(&self) -> Result<(), surrealdb::Error> {
+        let duplicates_query = r#"
+            SELECT normalized_email, array::group(id) AS users FROM (
+                SELECT id, string::lowercase(string::trim(email)) AS normalized_email FROM user
+            )
+            GROUP BY normalized_email
+        "#;
+
+        let mut response: surrealdb::Response = self.db.query(duplicates_query).await?;
+
+        let groups: Vec<NormalizedEmailGroup> = response.take(0)?;
+        let duplicates: Vec<String> = groups
+            .into_iter()
+            .filter(|group| group.users.len() > 1)
+            .map(|group| format!("{} ({} users)", group.normalized_email, group.users.len()))
+            .collect();
+
+        if !duplicates.is_empty() {
+            return Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(format!(
+                    "Users with emails that only differ by case have to be merged first: {}",
+                    duplicates.join(", ")
+                )),
+            ));
+        }
+
+        let update_query = r#"
+            UPDATE user
+            SET email = string::lowercase(string::trim(email))
+            WHERE email != string::lowercase(string::trim(email))
+        "#;
+
+        self.db.query(update_query).await?.check()?;
+
+        Ok(())
+    }
+
+    // Switching to a confirmed address also counts as verifying it
+    pub async fn update_email(
+        &self,
+        user_id: Thing,
+        email: String,
+    ) -> Result<User, surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET email = $email, email_verified = true
+            WHERE id = $user_id
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("email", normalize_email(&email)))
+            .await?;
+
+        let mut result: Vec<User> = response.take(0)?;
+
+        match result.pop() {
+            Some(user) => Ok(user),
+            None => Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(String::from("User doesn't exist")),
+            )),
+        }
+    }
+
+    pub async fn update_locale(
+        &self,
+        user_id: Thing,
+        locale: String,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET locale = $locale
+            WHERE id = $user_id
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("locale", locale))
+            .await?;
+
+        let result: Vec<User> = response.take(0)?;
+
+        if result.is_empty() {
+            return Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(String::from("User doesn't exist")),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Stores a new TOTP secret, 2FA stays disabled until the secret is confirmed with a valid code
+    pub async fn set_totp_secret(
+        &self,
+        user_id: Thing,
+        totp_secret: String,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET totp_secret = $totp_secret, totp_enabled = false, totp_setup_attempts = 0
+            WHERE id = $user_id
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("totp_secret", totp_secret))
+            .await?;
+
+        let result: Vec<User> = response.take(0)?;
+
+        if result.is_empty() {
+            return Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(String::from("User doesn't exist")),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // The step of the confirmation code is stored so the same code can't be used to sign in
+    pub async fn enable_totp(
+        &self,
+        user_id: Thing,
+        totp_step: i64,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET totp_enabled = true, totp_last_step = $totp_step
+            WHERE id = $user_id AND totp_secret != NONE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("totp_step", totp_step))
+            .await?;
+
+        let result: Vec<User> = response.take(0)?;
+
+        if result.is_empty() {
+            return Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(String::from(
+                    "User either doesn't exist or has no TOTP secret",
+                )),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Counts a code tried against the pending TOTP secret in the same update that checks the limit,
+    // returns false once the setup ran out of attempts
+    pub async fn consume_totp_setup_attempt(
+        &self,
+        user_id: Thing,
+        max_attempts: i64,
+    ) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET totp_setup_attempts += 1
+            WHERE id = $user_id AND totp_setup_attempts < $max_attempts
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("max_attempts", max_attempts))
+            .await?;
+
+        let result: Vec<User> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
+
+    // Stores the step of an accepted TOTP code in a single update, returns false when the step
+    // or a later one was already used
+    pub async fn accept_totp_step(
+        &self,
+        user_id: Thing,
+        totp_step: i64,
+    ) -> Result<bool, surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET totp_last_step = $totp_step
+            WHERE id = $user_id AND (totp_last_step = NONE OR totp_last_step < $totp_step)
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("totp_step", totp_step))
+            .await?;
+
+        let result: Vec<User> = response.take(0)?;
+
+        Ok(!result.is_empty())
+    }
+
+    pub async fn lock(
+        &self,
+        user_id: Thing,
+        locked_until: Datetime,
+        lock_count: i64,
+    ) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET locked_until = $locked_until, lock_count = $lock_count
+            WHERE id = $user_id
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("user_id", user_id))
+            .bind(("locked_until", locked_until))
+            .bind(("lock_count", lock_count))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+
+    // Lifts the lock and resets the lock count, so the next lock starts at the base duration again
+    pub async fn unlock(&self, user_id: Thing) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            UPDATE user
+            SET locked_until = NONE, lock_count = 0
+            WHERE id = $user_id
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("user_id", user_id))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
 }
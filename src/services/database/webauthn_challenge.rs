@@ -0,0 +1,129 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    engine::remote::ws::Client,
+    sql::{Datetime, Thing},
+    Surreal,
+};
+use validator::Validate;
+
+use crate::utils::{crypto::generate_token, webauthn::generate_challenge};
+
+pub const PURPOSE_REGISTRATION: &str = "registration";
+pub const PURPOSE_AUTHENTICATION: &str = "authentication";
+
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
+pub struct WebauthnChallenge {
+    pub id: Thing,
+    pub challenge: String,
+    pub purpose: String,
+
+    #[serde(default)]
+    pub created_at: Datetime,
+    #[serde(default)]
+    pub expires_at: Datetime,
+
+    #[serde(default)]
+    pub user: Option<Thing>,
+}
+
+#[derive(Clone)]
+pub struct WebauthnChallengeQuery<'a> {
+    db: &'a Surreal<Client>,
+}
+
+impl<'a> WebauthnChallengeQuery<'a> {
+    pub(crate) fn new(db: &'a Surreal<Client>) -> Self {
+        Self { db }
+    }
+}
+
+impl<'a> WebauthnChallengeQuery<'a> {
+    pub async fn create(
+        &self,
+        purpose: &str,
+        user_id: Option<Thing>,
+    ) -> Result<WebauthnChallenge, surrealdb::Error> {
+        let webauthn_challenge_id =
+            Thing::from(("webauthn_challenge".to_string(), generate_token()));
+
+        let now: DateTime<Utc> = Utc::now();
+        let expires: DateTime<Utc> = now + Duration::minutes(5);
+
+        let query = r#"
+            CREATE webauthn_challenge CONTENT {
+                id: $id,
+                challenge: $challenge,
+                purpose: $purpose,
+                created_at: $created_at,
+                expires_at: $expires_at,
+                user: $user
+            }
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("id", webauthn_challenge_id))
+            .bind(("challenge", generate_challenge()))
+            .bind(("purpose", purpose.to_string()))
+            .bind(("created_at", Datetime::from(now)))
+            .bind(("expires_at", Datetime::from(expires)))
+            .bind(("user", user_id))
+            .await?;
+
+        let created: Option<WebauthnChallenge> = response.take(0)?;
+
+        match created {
+            Some(webauthn_challenge) => Ok(webauthn_challenge),
+            None => Err(surrealdb::Error::Api(
+                surrealdb::error::Api::InvalidRequest(
+                    "Failed to create WebAuthn challenge".to_string(),
+                ),
+            )),
+        }
+    }
+
+    // Challenges are single use, so they are removed as soon as they are read
+    pub async fn consume(
+        &self,
+        webauthn_challenge_id: Thing,
+    ) -> Result<WebauthnChallenge, surrealdb::Error> {
+        let query = r#"
+            DELETE FROM webauthn_challenge
+            WHERE id = $webauthn_challenge_id
+            RETURN BEFORE
+        "#;
+
+        let mut response: surrealdb::Response = self
+            .db
+            .query(query)
+            .bind(("webauthn_challenge_id", webauthn_challenge_id))
+            .await?;
+
+        let mut result: Vec<WebauthnChallenge> = response.take(0)?;
+
+        match result.pop() {
+            Some(webauthn_challenge) => Ok(webauthn_challenge),
+            None => Err(surrealdb::Error::Api(surrealdb::error::Api::InvalidParams(
+                String::from("WebAuthn challenge either doesn't exist or was already used"),
+            ))),
+        }
+    }
+
+    // Challenges of abandoned ceremonies are never consumed
+    pub async fn remove_expired(&self) -> Result<(), surrealdb::Error> {
+        let query = r#"
+            DELETE FROM webauthn_challenge
+            WHERE expires_at < $now
+        "#;
+
+        self.db
+            .query(query)
+            .bind(("now", Datetime::from(Utc::now())))
+            .await?
+            .check()?;
+
+        Ok(())
+    }
+}
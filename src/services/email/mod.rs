@@ -1,18 +1,103 @@
 use axum::{body::Body, http::Request, response::Response};
+use chrono::{DateTime, Utc};
 use futures_util::future::BoxFuture;
-use resend_rs::{types::CreateEmailBaseOptions, Resend};
-use std::task::{Context, Poll};
+use serde_json::{json, Value};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::Notify;
 use tower::{Layer, Service};
 
+use crate::services::database::DatabaseLayer;
+
+mod templates;
+mod transport;
+
+pub use templates::{EmailBranding, EmailTemplates};
+pub use transport::{
+    EmailError, EmailMessage, EmailTransport, FileTransport, MemoryTransport, ResendTransport,
+    SmtpTransport,
+};
+
+// Month names aren't translated, so dates are written in a locale neutral format
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+// Emails are written to the `email_outbox` table and delivered by the outbox worker, so an
+// unavailable email provider delays emails instead of failing the requests sending them
 #[derive(Clone)]
 pub struct EmailLayer {
-    api_key: String,
+    transport: Arc<dyn EmailTransport>,
+    templates: EmailTemplates,
+    database_layer: DatabaseLayer,
+    outbox_notify: Arc<Notify>,
+    // Set when emails are captured in memory, exposed by the development mailbox
+    mailbox: Option<MemoryTransport>,
     pub domain: String,
 }
 
 impl EmailLayer {
-    pub fn new(api_key: String, domain: String) -> Self {
-        Self { api_key, domain }
+    pub fn new(
+        transport: Arc<dyn EmailTransport>,
+        templates: EmailTemplates,
+        database_layer: DatabaseLayer,
+        domain: String,
+    ) -> Self {
+        Self {
+            transport,
+            templates,
+            database_layer,
+            outbox_notify: Arc::new(Notify::new()),
+            mailbox: None,
+            domain,
+        }
+    }
+
+    pub fn with_mailbox(mut self, mailbox: MemoryTransport) -> Self {
+        self.mailbox = Some(mailbox);
+        self
+    }
+
+    pub fn mailbox(&self) -> Option<&MemoryTransport> {
+        self.mailbox.as_ref()
+    }
+
+    // Hands an email from the outbox to the transport
+    pub async fn deliver(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        self.transport.send(message).await
+    }
+
+    // Resolves once an email was queued, lets the outbox worker pick it up right away
+    pub async fn outbox_notified(&self) {
+        self.outbox_notify.notified().await
+    }
+
+    // Renders both the html and the plain text template so clients can pick either part, the
+    // rendering happens here so the email keeps the locale of the current request
+    async fn send(&self, to: String, template: &str, context: Value) -> Result<(), EmailError> {
+        let rendered = self
+            .templates
+            .render(template, context)
+            .map_err(EmailError::Template)?;
+
+        let message = EmailMessage {
+            from: format!("{} <noreply@{}>", self.templates.brand_name(), &self.domain),
+            to,
+            subject: rendered.subject,
+            html: rendered.html,
+            text: Some(rendered.text),
+            idempotency_key: None,
+        };
+
+        self.database_layer
+            .query()
+            .email_outbox
+            .enqueue(&message)
+            .await
+            .map_err(|err| EmailError::Outbox(Box::new(err)))?;
+        self.outbox_notify.notify_one();
+
+        Ok(())
     }
 
     pub async fn send_email_verification(
@@ -20,68 +105,177 @@ impl EmailLayer {
         to: String,
         activation_code: String,
         token_hash: String,
-    ) -> Result<(), resend_rs::Error> {
-        let resend = Resend::new(&self.api_key);
-
-        let from = format!("Orvane <noreply@{}>", &self.domain);
-        let to = [to];
-        let subject = "Orvane - Account Activation";
-
-        let email = CreateEmailBaseOptions::new(from, to, subject).with_html(
-            format!(
-                "
-                <strong>{}</strong>
-                <span>{}</span>
-            ",
-                activation_code, token_hash
-            )
-            .as_str(),
+    ) -> Result<(), EmailError> {
+        self.send(
+            to,
+            "email_verification",
+            json!({ "code": activation_code, "token_hash": token_hash }),
+        )
+        .await
+    }
+
+    pub async fn send_email_verification_confirmation(&self, to: String) -> Result<(), EmailError> {
+        self.send(to, "email_verification_confirmation", json!({}))
+            .await
+    }
+
+    pub async fn send_password_reset(
+        &self,
+        to: String,
+        password_reset_request_id: String,
+    ) -> Result<(), EmailError> {
+        let password_reset_url = format!(
+            "https://{}/auth/password-reset/{}",
+            &self.domain, password_reset_request_id
         );
 
-        let _email = resend.emails.send(email).await?;
+        self.send(
+            to,
+            "password_reset",
+            json!({ "password_reset_url": password_reset_url }),
+        )
+        .await
+    }
+
+    pub async fn send_password_reset_confirmation(&self, to: String) -> Result<(), EmailError> {
+        self.send(to, "password_reset_confirmation", json!({}))
+            .await
+    }
 
-        Ok(())
+    pub async fn send_password_changed(&self, to: String) -> Result<(), EmailError> {
+        self.send(to, "password_changed", json!({})).await
     }
 
-    pub async fn send_email_verification_confirmation(
+    pub async fn send_email_change_verification(
         &self,
         to: String,
-    ) -> Result<(), resend_rs::Error> {
-        let resend = Resend::new(&self.api_key);
+        verification_code: String,
+        token_hash: String,
+    ) -> Result<(), EmailError> {
+        self.send(
+            to,
+            "email_change_verification",
+            json!({ "code": verification_code, "token_hash": token_hash }),
+        )
+        .await
+    }
 
-        let from = format!("Orvane <noreply@{}>", &self.domain);
-        let to = [to];
-        let subject = "Orvane - Email verified successfully";
+    pub async fn send_email_change_requested(
+        &self,
+        to: String,
+        new_email: String,
+        cancel_token: String,
+    ) -> Result<(), EmailError> {
+        let cancel_url = format!(
+            "https://{}/account/email/cancel/{}",
+            &self.domain, cancel_token
+        );
 
-        let email = CreateEmailBaseOptions::new(from, to, subject).with_html("Email verified!");
+        self.send(
+            to,
+            "email_change_requested",
+            json!({ "new_email": new_email, "cancel_url": cancel_url }),
+        )
+        .await
+    }
 
-        let _email = resend.emails.send(email).await?;
+    pub async fn send_email_changed(
+        &self,
+        to: String,
+        new_email: String,
+    ) -> Result<(), EmailError> {
+        self.send(to, "email_changed", json!({ "new_email": new_email }))
+            .await
+    }
 
-        Ok(())
+    pub async fn send_account_deletion_scheduled(
+        &self,
+        to: String,
+        scheduled_at: DateTime<Utc>,
+        cancel_token: String,
+    ) -> Result<(), EmailError> {
+        let cancel_url = format!(
+            "https://{}/account/deletion/cancel/{}",
+            &self.domain, cancel_token
+        );
+
+        self.send(
+            to,
+            "account_deletion_scheduled",
+            json!({
+                "scheduled_at": scheduled_at.format(DATE_FORMAT).to_string(),
+                "cancel_url": cancel_url,
+            }),
+        )
+        .await
     }
 
-    pub async fn send_password_reset(
+    pub async fn send_account_deleted(&self, to: String) -> Result<(), EmailError> {
+        self.send(to, "account_deleted", json!({})).await
+    }
+
+    pub async fn send_data_export(
         &self,
         to: String,
-        password_reset_request_id: String,
-    ) -> Result<(), resend_rs::Error> {
-        let resend = Resend::new(&self.api_key);
+        token: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), EmailError> {
+        let download_url = format!("https://{}/api/v1/account/export/{}", &self.domain, token);
 
-        let from = format!("Orvane <noreply@{}>", &self.domain);
-        let to = [to];
-        let subject = "Orvane - Password Reset";
+        self.send(
+            to,
+            "data_export",
+            json!({
+                "download_url": download_url,
+                "expires_at": expires_at.format(DATE_FORMAT).to_string(),
+            }),
+        )
+        .await
+    }
 
-        let password_reset_url = format!(
-            "https://{}/auth/password-reset/{}",
-            &self.domain, password_reset_request_id
-        );
+    pub async fn send_magic_link(
+        &self,
+        to: String,
+        code: String,
+        token: String,
+    ) -> Result<(), EmailError> {
+        let magic_link_url = format!("https://{}/auth/magic-link/{}", &self.domain, token);
 
-        let email = CreateEmailBaseOptions::new(from, to, subject)
-            .with_html(format!("<a href=\"{}\">Reset</strong>", password_reset_url).as_str());
+        self.send(
+            to,
+            "magic_link",
+            json!({ "magic_link_url": magic_link_url, "code": code }),
+        )
+        .await
+    }
 
-        let _email = resend.emails.send(email).await?;
+    pub async fn send_recovery_code_used(
+        &self,
+        to: String,
+        remaining_codes: usize,
+    ) -> Result<(), EmailError> {
+        self.send(
+            to,
+            "recovery_code_used",
+            json!({ "remaining_codes": remaining_codes }),
+        )
+        .await
+    }
 
-        Ok(())
+    pub async fn send_account_locked(
+        &self,
+        to: String,
+        locked_minutes: i64,
+        unlock_token: String,
+    ) -> Result<(), EmailError> {
+        let unlock_url = format!("https://{}/auth/unlock/{}", &self.domain, unlock_token);
+
+        self.send(
+            to,
+            "account_locked",
+            json!({ "locked_minutes": locked_minutes, "unlock_url": unlock_url }),
+        )
+        .await
     }
 }
 
@@ -89,19 +283,13 @@ impl<S> Layer<S> for EmailLayer {
     type Service = EmailService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        EmailService {
-            inner,
-            api_key: self.api_key.clone(),
-            domain: self.domain.clone(),
-        }
+        EmailService { inner }
     }
 }
 
 #[derive(Clone)]
 pub struct EmailService<S> {
     pub inner: S,
-    pub domain: String,
-    api_key: String,
 }
 
 impl<S> Service<Request<Body>> for EmailService<S>
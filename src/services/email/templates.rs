@@ -0,0 +1,344 @@
+use std::{path::PathBuf, sync::Arc};
+
+use minijinja::{context, value::Kwargs, Environment, Value};
+use serde::Serialize;
+
+use crate::services::i18n::{current_locale, translate_with_args, FluentArgs};
+
+// Every template ships with the binary, a template directory only needs the files it overrides
+const EMBEDDED_TEMPLATES: &[(&str, &str)] = &[
+    ("layout.html", include_str!("templates/layout.html")),
+    ("layout.txt", include_str!("templates/layout.txt")),
+    ("macros.html", include_str!("templates/macros.html")),
+    (
+        "account_deleted.html",
+        include_str!("templates/account_deleted.html"),
+    ),
+    (
+        "account_deleted.txt",
+        include_str!("templates/account_deleted.txt"),
+    ),
+    (
+        "account_deletion_scheduled.html",
+        include_str!("templates/account_deletion_scheduled.html"),
+    ),
+    (
+        "account_deletion_scheduled.txt",
+        include_str!("templates/account_deletion_scheduled.txt"),
+    ),
+    (
+        "account_locked.html",
+        include_str!("templates/account_locked.html"),
+    ),
+    (
+        "account_locked.txt",
+        include_str!("templates/account_locked.txt"),
+    ),
+    (
+        "data_export.html",
+        include_str!("templates/data_export.html"),
+    ),
+    ("data_export.txt", include_str!("templates/data_export.txt")),
+    (
+        "email_change_requested.html",
+        include_str!("templates/email_change_requested.html"),
+    ),
+    (
+        "email_change_requested.txt",
+        include_str!("templates/email_change_requested.txt"),
+    ),
+    (
+        "email_change_verification.html",
+        include_str!("templates/email_change_verification.html"),
+    ),
+    (
+        "email_change_verification.txt",
+        include_str!("templates/email_change_verification.txt"),
+    ),
+    (
+        "email_changed.html",
+        include_str!("templates/email_changed.html"),
+    ),
+    (
+        "email_changed.txt",
+        include_str!("templates/email_changed.txt"),
+    ),
+    (
+        "email_verification.html",
+        include_str!("templates/email_verification.html"),
+    ),
+    (
+        "email_verification.txt",
+        include_str!("templates/email_verification.txt"),
+    ),
+    (
+        "email_verification_confirmation.html",
+        include_str!("templates/email_verification_confirmation.html"),
+    ),
+    (
+        "email_verification_confirmation.txt",
+        include_str!("templates/email_verification_confirmation.txt"),
+    ),
+    ("magic_link.html", include_str!("templates/magic_link.html")),
+    ("magic_link.txt", include_str!("templates/magic_link.txt")),
+    (
+        "password_changed.html",
+        include_str!("templates/password_changed.html"),
+    ),
+    (
+        "password_changed.txt",
+        include_str!("templates/password_changed.txt"),
+    ),
+    (
+        "password_reset.html",
+        include_str!("templates/password_reset.html"),
+    ),
+    (
+        "password_reset.txt",
+        include_str!("templates/password_reset.txt"),
+    ),
+    (
+        "password_reset_confirmation.html",
+        include_str!("templates/password_reset_confirmation.html"),
+    ),
+    (
+        "password_reset_confirmation.txt",
+        include_str!("templates/password_reset_confirmation.txt"),
+    ),
+    (
+        "recovery_code_used.html",
+        include_str!("templates/recovery_code_used.html"),
+    ),
+    (
+        "recovery_code_used.txt",
+        include_str!("templates/recovery_code_used.txt"),
+    ),
+];
+
+// Available to every template as `brand`
+#[derive(Serialize, Debug, Clone)]
+pub struct EmailBranding {
+    pub name: String,
+    pub primary_color: String,
+    pub logo_url: Option<String>,
+    pub support_email: Option<String>,
+}
+
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+// Each email is a `<name>.txt` template defining the `subject` and `content` blocks of the plain
+// text part and a `<name>.html` template defining the `content` block, the subject is available to
+// it as `subject`. Both extend their layout. The copy itself
+// comes from the `emails.ftl` translations through `t`, in the locale of the current request
+#[derive(Clone)]
+pub struct EmailTemplates {
+    environment: Arc<Environment<'static>>,
+    brand_name: String,
+}
+
+impl EmailTemplates {
+    // Templates found in `override_dir` take precedence over the embedded ones
+    pub fn new(branding: EmailBranding, override_dir: Option<PathBuf>) -> Self {
+        let mut environment = Environment::new();
+
+        environment.set_trim_blocks(true);
+        environment.set_lstrip_blocks(true);
+        environment.add_global("brand", Value::from_serialize(&branding));
+        environment.add_function("t", translate_function);
+        environment.add_function("locale", || current_locale().code());
+        environment.set_loader(move |name| {
+            if let Some(dir) = &override_dir {
+                match std::fs::read_to_string(dir.join(name)) {
+                    Ok(source) => return Ok(Some(source)),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => {
+                        return Err(minijinja::Error::new(
+                            minijinja::ErrorKind::InvalidOperation,
+                            "email template couldn't be read",
+                        )
+                        .with_source(err))
+                    }
+                }
+            }
+
+            Ok(EMBEDDED_TEMPLATES
+                .iter()
+                .find(|(embedded_name, _)| *embedded_name == name)
+                .map(|(_, source)| source.to_string()))
+        });
+
+        Self {
+            environment: Arc::new(environment),
+            brand_name: branding.name,
+        }
+    }
+
+    pub fn brand_name(&self) -> &str {
+        &self.brand_name
+    }
+
+    pub fn render<S: Serialize>(
+        &self,
+        name: &str,
+        context: S,
+    ) -> Result<RenderedEmail, minijinja::Error> {
+        let context = Value::from_serialize(&context);
+
+        // The subject comes from the plain text template, the html one would escape it
+        let mut text = self
+            .environment
+            .get_template(&format!("{}.txt", name))?
+            .render_captured(&context)?;
+        let subject = text.with_state_mut(|state| state.render_block("subject"))?;
+        let subject = subject.trim();
+
+        let html = self
+            .environment
+            .get_template(&format!("{}.html", name))?
+            .render(context! { subject, ..context })?;
+
+        Ok(RenderedEmail {
+            subject: format!("{} - {}", self.brand_name, subject),
+            html,
+            text: text.into_output(),
+        })
+    }
+}
+
+// `t("key", name=value)` looks the message up in the current locale, numbers stay numbers so
+// plural variants can be selected
+fn translate_function(key: &str, kwargs: Kwargs) -> Result<String, minijinja::Error> {
+    let mut args = FluentArgs::new();
+
+    for name in kwargs.args() {
+        let value: Value = kwargs.get(name)?;
+
+        match i64::try_from(value.clone()) {
+            Ok(number) if value.is_number() => args.set(name, number),
+            _ => args.set(name, value.to_string()),
+        }
+    }
+
+    Ok(translate_with_args(key, &args))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::services::i18n::{with_locale, Locale};
+
+    fn branding() -> EmailBranding {
+        EmailBranding {
+            name: String::from("Orvane"),
+            primary_color: String::from("#4f46e5"),
+            logo_url: None,
+            support_email: Some(String::from("support@orvane.test")),
+        }
+    }
+
+    fn templates() -> EmailTemplates {
+        EmailTemplates::new(branding(), None)
+    }
+
+    fn verification_context() -> serde_json::Value {
+        json!({ "code": "123456", "token_hash": "abc123" })
+    }
+
+    #[test]
+    fn renders_every_part_of_an_email() {
+        let rendered = templates()
+            .render("email_verification", verification_context())
+            .unwrap();
+
+        assert_eq!(rendered.subject, "Orvane - Account Activation");
+        assert!(rendered.html.contains("123456"));
+        assert!(rendered.text.contains("123456"));
+        assert!(rendered.text.contains("Verification reference: abc123"));
+        assert!(rendered.text.contains("support@orvane.test"));
+        assert!(!rendered.text.contains('<'));
+    }
+
+    #[tokio::test]
+    async fn renders_in_the_current_locale() {
+        let rendered = with_locale(Locale::De, async {
+            templates().render("email_verification", verification_context())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(rendered.subject, "Orvane - Kontoaktivierung");
+        assert!(rendered.text.contains("Bestätigungsreferenz: abc123"));
+    }
+
+    #[tokio::test]
+    async fn subject_is_not_html_escaped() {
+        let templates = EmailTemplates::new(
+            EmailBranding {
+                name: String::from("Smith & Sons"),
+                ..branding()
+            },
+            None,
+        );
+
+        let rendered = with_locale(Locale::Fr, async {
+            templates.render(
+                "email_change_requested",
+                json!({ "new_email": "jane@example.com", "cancel_url": "https://orvane.test" }),
+            )
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            rendered.subject,
+            "Smith & Sons - Changement d'adresse e-mail demandé"
+        );
+        assert!(rendered
+            .html
+            .contains("Smith &amp; Sons - Changement d&#x27;adresse"));
+    }
+
+    #[test]
+    fn renders_every_embedded_template() {
+        let templates = templates();
+        let names = EMBEDDED_TEMPLATES
+            .iter()
+            .filter_map(|(name, _)| name.strip_suffix(".html"))
+            .filter(|name| !["layout", "macros"].contains(name));
+
+        for name in names {
+            let rendered = templates.render(name, json!({})).unwrap();
+
+            assert!(
+                rendered.subject.len() > "Orvane - ".len(),
+                "{} has no subject",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn override_dir_takes_precedence() {
+        let dir = std::env::temp_dir().join(format!("email-templates-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("email_verification.html"),
+            "<p>{{ subject }}: {{ code }}</p>",
+        )
+        .unwrap();
+
+        let rendered = EmailTemplates::new(branding(), Some(dir.clone()))
+            .render("email_verification", verification_context())
+            .unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(rendered.html, "<p>Account Activation: 123456</p>");
+        assert!(rendered.text.contains("123456"));
+    }
+}
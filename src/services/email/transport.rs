@@ -0,0 +1,316 @@
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+use derive_more::Display;
+use futures_util::future::BoxFuture;
+use hyper::header::CONTENT_TYPE;
+use lettre::{
+    message::{header::ContentType, Mailbox, MultiPart, SinglePart},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use serde_json::json;
+
+const RESEND_API_URL: &str = "https://api.resend.com/emails";
+
+#[derive(Debug, Display)]
+pub enum EmailError {
+    Http(reqwest::Error),
+    // Status and body of a request the Resend API refused
+    Resend(String),
+    Smtp(lettre::transport::smtp::Error),
+    Address(lettre::address::AddressError),
+    Message(lettre::error::Error),
+    Template(minijinja::Error),
+    // Boxed since the database error would make every email result large
+    Outbox(Box<surrealdb::Error>),
+    Io(std::io::Error),
+    Timeout,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub from: String,
+    pub to: String,
+    pub subject: String,
+    pub html: String,
+    pub text: Option<String>,
+    // Stays the same across delivery attempts of one email so duplicates can be recognized
+    pub idempotency_key: Option<String>,
+}
+
+// Delivers the rendered emails, `EmailLayer` decides what gets sent and the transport how
+pub trait EmailTransport: Send + Sync {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), EmailError>>;
+}
+
+// Talks to the API directly since the SDK can't send the `Idempotency-Key` header, with it Resend
+// drops retries of an email that was already accepted
+pub struct ResendTransport {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl ResendTransport {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: api_key.to_string(),
+        }
+    }
+}
+
+impl EmailTransport for ResendTransport {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), EmailError>> {
+        Box::pin(async move {
+            let mut email = json!({
+                "from": message.from,
+                "to": [message.to],
+                "subject": message.subject,
+                "html": message.html,
+            });
+
+            if let Some(text) = &message.text {
+                email["text"] = json!(text);
+            }
+
+            let mut request = self
+                .client
+                .post(RESEND_API_URL)
+                .bearer_auth(&self.api_key)
+                .header(CONTENT_TYPE, "application/json")
+                .body(email.to_string());
+
+            if let Some(idempotency_key) = &message.idempotency_key {
+                request = request.header("Idempotency-Key", idempotency_key);
+            }
+
+            let response = request.send().await.map_err(EmailError::Http)?;
+            let status = response.status();
+
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+
+                return Err(EmailError::Resend(format!("{} {}", status, body)));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+pub struct SmtpTransport {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpTransport {
+    // Without credentials the connection stays unencrypted, which is what local SMTP sinks expect
+    pub fn new(
+        host: &str,
+        port: u16,
+        credentials: Option<(String, String)>,
+    ) -> Result<Self, EmailError> {
+        let transport = match credentials {
+            Some((username, password)) => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                    .map_err(EmailError::Smtp)?
+                    .port(port)
+                    .credentials(Credentials::new(username, password))
+                    .build()
+            }
+            None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+                .port(port)
+                .build(),
+        };
+
+        Ok(Self { transport })
+    }
+}
+
+impl EmailTransport for SmtpTransport {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), EmailError>> {
+        Box::pin(async move {
+            self.transport
+                .send(build_mime_message(message)?)
+                .await
+                .map_err(EmailError::Smtp)?;
+
+            Ok(())
+        })
+    }
+}
+
+// Writes every email as an `.eml` file, which any mail client can open
+pub struct FileTransport {
+    path: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl EmailTransport for FileTransport {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), EmailError>> {
+        Box::pin(async move {
+            let file_name = format!(
+                "{}-{}.eml",
+                Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+                uuid::Uuid::new_v4()
+            );
+
+            tokio::fs::create_dir_all(&self.path)
+                .await
+                .map_err(EmailError::Io)?;
+            tokio::fs::write(
+                self.path.join(file_name),
+                build_mime_message(message)?.formatted(),
+            )
+            .await
+            .map_err(EmailError::Io)?;
+
+            Ok(())
+        })
+    }
+}
+
+// Keeps the sent emails around instead of delivering them, clones share the same mailbox
+#[derive(Clone, Default)]
+pub struct MemoryTransport {
+    messages: Arc<Mutex<Vec<EmailMessage>>>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn messages(&self) -> Vec<EmailMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+impl EmailTransport for MemoryTransport {
+    fn send<'a>(&'a self, message: &'a EmailMessage) -> BoxFuture<'a, Result<(), EmailError>> {
+        Box::pin(async move {
+            self.messages.lock().unwrap().push(message.clone());
+
+            Ok(())
+        })
+    }
+}
+
+fn build_mime_message(message: &EmailMessage) -> Result<Message, EmailError> {
+    let from: Mailbox = message.from.parse().map_err(EmailError::Address)?;
+    let message_id = message
+        .idempotency_key
+        .as_ref()
+        .map(|key| format!("<{}@{}>", key, from.email.domain()));
+
+    let builder = Message::builder()
+        .from(from)
+        .to(message.to.parse().map_err(EmailError::Address)?)
+        .subject(&message.subject)
+        .message_id(message_id);
+
+    let html = SinglePart::builder()
+        .header(ContentType::TEXT_HTML)
+        .body(message.html.clone());
+
+    let result = match &message.text {
+        Some(text) => builder.multipart(
+            MultiPart::alternative()
+                .singlepart(
+                    SinglePart::builder()
+                        .header(ContentType::TEXT_PLAIN)
+                        .body(text.clone()),
+                )
+                .singlepart(html),
+        ),
+        None => builder.singlepart(html),
+    };
+
+    result.map_err(EmailError::Message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(text: Option<&str>, idempotency_key: Option<&str>) -> EmailMessage {
+        EmailMessage {
+            from: String::from("Orvane <noreply@orvane.test>"),
+            to: String::from("jane@example.com"),
+            subject: String::from("Orvane - Account Activation"),
+            html: String::from("<p>Your code is 123456</p>"),
+            text: text.map(String::from),
+            idempotency_key: idempotency_key.map(String::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_transport_captures_messages() {
+        let transport = MemoryTransport::new();
+        let mailbox = transport.clone();
+
+        transport.send(&message(None, None)).await.unwrap();
+        transport
+            .send(&message(Some("Your code is 654321"), None))
+            .await
+            .unwrap();
+
+        let messages = mailbox.messages();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].to, "jane@example.com");
+        assert_eq!(messages[1].text.as_deref(), Some("Your code is 654321"));
+    }
+
+    #[test]
+    fn mime_message_has_text_and_html_alternatives() {
+        let formatted = build_mime_message(&message(Some("Your code is 123456"), None))
+            .unwrap()
+            .formatted();
+        let formatted = String::from_utf8(formatted).unwrap();
+
+        assert!(formatted.contains("multipart/alternative"));
+        assert!(formatted.contains("text/plain"));
+        assert!(formatted.contains("text/html"));
+    }
+
+    #[test]
+    fn mime_message_without_text_is_html_only() {
+        let formatted = build_mime_message(&message(None, None))
+            .unwrap()
+            .formatted();
+        let formatted = String::from_utf8(formatted).unwrap();
+
+        assert!(!formatted.contains("multipart"));
+        assert!(formatted.contains("text/html"));
+    }
+
+    #[test]
+    fn mime_message_id_comes_from_idempotency_key() {
+        let formatted = build_mime_message(&message(None, Some("email_outbox-42")))
+            .unwrap()
+            .formatted();
+        let formatted = String::from_utf8(formatted).unwrap();
+
+        assert!(formatted.contains("Message-ID: <email_outbox-42@orvane.test>"));
+    }
+
+    #[test]
+    fn mime_message_rejects_invalid_recipient() {
+        let mut message = message(None, None);
+        message.to = String::from("not an address");
+
+        assert!(matches!(
+            build_mime_message(&message),
+            Err(EmailError::Address(_))
+        ));
+    }
+}
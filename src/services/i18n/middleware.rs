@@ -0,0 +1,17 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+use hyper::header::ACCEPT_LANGUAGE;
+
+use super::{with_locale, Locale};
+
+// Handles the request in the best match of the `Accept-Language` header, loading the session
+// switches to the locale the signed in user picked, see `set_current_locale`
+pub async fn locale_middleware(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|header| header.to_str().ok())
+        .and_then(Locale::negotiate)
+        .unwrap_or_default();
+
+    with_locale(locale, next.run(request)).await
+}
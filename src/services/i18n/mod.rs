@@ -0,0 +1,226 @@
+use std::{cell::Cell, collections::HashMap, future::Future};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentResource};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+mod middleware;
+
+pub use fluent_bundle::FluentArgs;
+pub use middleware::locale_middleware;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+    Pt,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 5] = [Locale::En, Locale::De, Locale::Fr, Locale::Es, Locale::Pt];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+            Locale::Es => "es",
+            Locale::Pt => "pt",
+        }
+    }
+
+    // Only the language matters, regional variants like `pt-BR` fall back to their language
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let language = code.split(['-', '_']).next()?.trim().to_lowercase();
+
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+    }
+
+    // Picks the supported locale with the highest quality from an `Accept-Language` header
+    pub fn negotiate(accept_language: &str) -> Option<Locale> {
+        let mut candidates: Vec<(f32, Locale)> = accept_language
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.trim().split(';');
+                let locale = Locale::from_code(parts.next()?)?;
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map(|quality| quality.parse().unwrap_or(0.0))
+                    .unwrap_or(1.0);
+
+                (quality > 0.0).then_some((quality, locale))
+            })
+            .collect();
+
+        // Stable sort keeps the header order for equal qualities
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates.first().map(|(_, locale)| *locale)
+    }
+
+    fn resources(&self) -> [&'static str; 2] {
+        match self {
+            Locale::En => [
+                include_str!("../../locales/en/errors.ftl"),
+                include_str!("../../locales/en/emails.ftl"),
+            ],
+            Locale::De => [
+                include_str!("../../locales/de/errors.ftl"),
+                include_str!("../../locales/de/emails.ftl"),
+            ],
+            Locale::Fr => [
+                include_str!("../../locales/fr/errors.ftl"),
+                include_str!("../../locales/fr/emails.ftl"),
+            ],
+            Locale::Es => [
+                include_str!("../../locales/es/errors.ftl"),
+                include_str!("../../locales/es/emails.ftl"),
+            ],
+            Locale::Pt => [
+                include_str!("../../locales/pt/errors.ftl"),
+                include_str!("../../locales/pt/emails.ftl"),
+            ],
+        }
+    }
+}
+
+lazy_static! {
+    static ref TRANSLATIONS: HashMap<Locale, FluentBundle<FluentResource>> = Locale::ALL
+        .into_iter()
+        .map(|locale| (locale, load_bundle(locale)))
+        .collect();
+}
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Cell<Locale>;
+}
+
+fn load_bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = locale.code().parse().unwrap();
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+
+    // Isolation marks would end up as invisible characters in emails and JSON responses
+    bundle.set_use_isolating(false);
+
+    for source in locale.resources() {
+        let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|_| {
+            panic!(
+                "Translations for locale {} couldn't be parsed",
+                locale.code()
+            )
+        });
+
+        bundle.add_resource(resource).unwrap_or_else(|_| {
+            panic!(
+                "Translations for locale {} contain duplicates",
+                locale.code()
+            )
+        });
+    }
+
+    bundle
+}
+
+// Parses every translation up front so a broken resource file fails the startup
+pub fn load_translations() {
+    lazy_static::initialize(&TRANSLATIONS);
+}
+
+// Locale of the request being handled, set by `locale_middleware`
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE
+        .try_with(|locale| locale.get())
+        .unwrap_or_default()
+}
+
+// Switches the locale of the request being handled, used once the signed in user is known.
+// Does nothing outside of `with_locale`
+pub fn set_current_locale(locale: Locale) {
+    let _ = CURRENT_LOCALE.try_with(|current| current.set(locale));
+}
+
+// Runs the future with the provided locale, for work that happens outside of a request
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    CURRENT_LOCALE.scope(Cell::new(locale), future).await
+}
+
+pub fn translate(key: &str) -> String {
+    translate_with_args(key, &FluentArgs::new())
+}
+
+// Messages missing from the current locale fall back to English, and to the key itself
+pub fn translate_with_args(key: &str, args: &FluentArgs) -> String {
+    [current_locale(), Locale::En]
+        .into_iter()
+        .find_map(|locale| {
+            let bundle = TRANSLATIONS.get(&locale)?;
+            let pattern = bundle.get_message(key)?.value()?;
+            let mut errors = vec![];
+
+            Some(
+                bundle
+                    .format_pattern(pattern, Some(args), &mut errors)
+                    .into_owned(),
+            )
+        })
+        .unwrap_or_else(|| key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_code_ignores_region_and_case() {
+        assert_eq!(Locale::from_code("de"), Some(Locale::De));
+        assert_eq!(Locale::from_code("pt-BR"), Some(Locale::Pt));
+        assert_eq!(Locale::from_code("FR_ca"), Some(Locale::Fr));
+        assert_eq!(Locale::from_code("it"), None);
+        assert_eq!(Locale::from_code(""), None);
+    }
+
+    #[test]
+    fn negotiate_picks_the_highest_quality() {
+        assert_eq!(
+            Locale::negotiate("fr;q=0.5, es;q=0.9, de;q=0.7"),
+            Some(Locale::Es)
+        );
+        assert_eq!(Locale::negotiate("de, fr;q=0.9"), Some(Locale::De));
+    }
+
+    #[test]
+    fn negotiate_keeps_header_order_for_equal_qualities() {
+        assert_eq!(Locale::negotiate("fr;q=0.8, de;q=0.8"), Some(Locale::Fr));
+    }
+
+    #[test]
+    fn negotiate_maps_regional_variants_to_their_language() {
+        assert_eq!(Locale::negotiate("pt-BR,pt;q=0.9"), Some(Locale::Pt));
+    }
+
+    #[test]
+    fn negotiate_skips_rejected_and_unsupported_locales() {
+        assert_eq!(Locale::negotiate("de;q=0, it, fr;q=0.1"), Some(Locale::Fr));
+        assert_eq!(Locale::negotiate("de;q=0"), None);
+        assert_eq!(Locale::negotiate("it, ja;q=0.8, *;q=0.1"), None);
+        assert_eq!(Locale::negotiate(""), None);
+    }
+
+    #[tokio::test]
+    async fn set_current_locale_switches_the_scoped_locale() {
+        let locale = with_locale(Locale::De, async {
+            set_current_locale(Locale::Pt);
+            current_locale()
+        })
+        .await;
+
+        assert_eq!(locale, Locale::Pt);
+        assert_eq!(current_locale(), Locale::En);
+    }
+}
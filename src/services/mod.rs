@@ -1,2 +1,4 @@
+pub mod breached_password;
 pub mod database;
 pub mod email;
+pub mod i18n;
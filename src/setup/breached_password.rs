@@ -0,0 +1,28 @@
+use std::{env, path::PathBuf, sync::Arc};
+
+use crate::services::breached_password::{
+    BreachedPasswordMode, BreachedPasswordScreening, RangeApiChecker, RangeDirectoryChecker,
+};
+
+pub fn setup_breached_password_screening() -> BreachedPasswordScreening {
+    let mode = match env::var("BREACHED_PASSWORD_CHECK").as_deref() {
+        Ok("warn") => BreachedPasswordMode::Warn,
+        Ok("reject") => BreachedPasswordMode::Reject,
+        _ => return BreachedPasswordScreening::disabled(),
+    };
+
+    // A local copy of the range files keeps the check offline, otherwise the public API is used
+    match env::var("BREACHED_PASSWORD_RANGE_DIR") {
+        Ok(path) => BreachedPasswordScreening::new(
+            mode,
+            Arc::new(RangeDirectoryChecker::new(PathBuf::from(path))),
+        ),
+        Err(_) => BreachedPasswordScreening::new(
+            mode,
+            Arc::new(RangeApiChecker::new(
+                env::var("BREACHED_PASSWORD_API_URL")
+                    .unwrap_or_else(|_| String::from("https://api.pwnedpasswords.com")),
+            )),
+        ),
+    }
+}
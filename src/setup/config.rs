@@ -0,0 +1,147 @@
+use std::{env, net::IpAddr};
+
+use chrono::{Duration, Utc};
+
+use crate::{services::database::user::User, utils::validation::PasswordPolicy};
+
+#[derive(Clone)]
+pub struct AppConfig {
+    // Key used to encrypt TOTP secrets at rest, 32 bytes for AES-256-GCM
+    pub totp_encryption_key: [u8; 32],
+    // WebAuthn relying party, the id has to match the domain the frontend is served from
+    pub webauthn_rp_id: String,
+    pub webauthn_rp_name: String,
+    pub webauthn_origin: String,
+    pub signin_lockout: SigninLockoutPolicy,
+    pub email_verification_policy: EmailVerificationPolicy,
+    pub password_policy: PasswordPolicy,
+    // Days between a deletion request and the account being permanently removed
+    pub account_deletion_grace_days: i64,
+    // Reverse proxies whose forwarded client address headers are trusted
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+#[derive(Clone)]
+pub struct SigninLockoutPolicy {
+    // Failed password attempts within the window that lock the account
+    pub max_failed_attempts: usize,
+    // Failed password attempts from a single IP address within the window, across all accounts
+    pub max_failed_attempts_per_ip: usize,
+    pub attempt_window_minutes: i64,
+    // The lock duration doubles with every consecutive lock, up to the maximum
+    pub base_lock_minutes: i64,
+    pub max_lock_minutes: i64,
+}
+
+// How signins of users who haven't verified their email yet are treated
+#[derive(Clone)]
+pub enum EmailVerificationPolicy {
+    Block,
+    // Unverified users only get unauthorized sessions, enough to finish the email verification
+    Restricted,
+    // Unverified users get full access for a number of hours after signup, restricted afterwards
+    GracePeriod { hours: i64 },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VerificationAccess {
+    Full,
+    Restricted,
+    Denied,
+}
+
+impl EmailVerificationPolicy {
+    pub fn access(&self, user: &User) -> VerificationAccess {
+        if user.email_verified {
+            return VerificationAccess::Full;
+        }
+
+        match self {
+            EmailVerificationPolicy::Block => VerificationAccess::Denied,
+            EmailVerificationPolicy::Restricted => VerificationAccess::Restricted,
+            EmailVerificationPolicy::GracePeriod { hours } => {
+                if *user.created_at + Duration::hours(*hours) > Utc::now() {
+                    VerificationAccess::Full
+                } else {
+                    VerificationAccess::Restricted
+                }
+            }
+        }
+    }
+}
+
+pub fn setup_config() -> AppConfig {
+    let webauthn_rp_id = env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| String::from("blazar.lol"));
+    let webauthn_origin =
+        env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| format!("https://{}", webauthn_rp_id));
+
+    AppConfig {
+        totp_encryption_key: load_totp_encryption_key(),
+        webauthn_rp_id,
+        webauthn_rp_name: String::from("Orvane"),
+        webauthn_origin,
+        signin_lockout: SigninLockoutPolicy {
+            max_failed_attempts: env_or("SIGNIN_MAX_FAILED_ATTEMPTS", 5),
+            max_failed_attempts_per_ip: env_or("SIGNIN_MAX_FAILED_ATTEMPTS_PER_IP", 20),
+            attempt_window_minutes: env_or("SIGNIN_ATTEMPT_WINDOW_MINUTES", 15),
+            base_lock_minutes: env_or("SIGNIN_BASE_LOCK_MINUTES", 15),
+            max_lock_minutes: env_or("SIGNIN_MAX_LOCK_MINUTES", 24 * 60),
+        },
+        email_verification_policy: load_email_verification_policy(),
+        password_policy: PasswordPolicy {
+            min_length: env_or("PASSWORD_MIN_LENGTH", 10),
+            max_length: env_or("PASSWORD_MAX_LENGTH", 128),
+            min_score: env_or("PASSWORD_MIN_SCORE", 3),
+        },
+        account_deletion_grace_days: env_or("ACCOUNT_DELETION_GRACE_DAYS", 14),
+        trusted_proxies: load_trusted_proxies(),
+    }
+}
+
+fn load_email_verification_policy() -> EmailVerificationPolicy {
+    match env::var("EMAIL_VERIFICATION_POLICY").as_deref() {
+        Ok("block") => EmailVerificationPolicy::Block,
+        Ok("grace") => EmailVerificationPolicy::GracePeriod {
+            hours: env_or("EMAIL_VERIFICATION_GRACE_HOURS", 72),
+        },
+        Ok("restricted") | Err(_) => EmailVerificationPolicy::Restricted,
+        Ok(policy) => {
+            println!(
+                "Unknown email verification policy \"{}\", using \"restricted\"",
+                policy
+            );
+            EmailVerificationPolicy::Restricted
+        }
+    }
+}
+
+// Comma separated addresses, without any proxy the forwarded headers are ignored entirely
+fn load_trusted_proxies() -> Vec<IpAddr> {
+    env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(|address| {
+            address.parse().unwrap_or_else(|_| {
+                panic!("TRUSTED_PROXIES contains an invalid address: {}", address)
+            })
+        })
+        .collect()
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+// Secrets encrypted with a temporary key couldn't be decrypted after a restart, so a missing
+// or malformed key stops the startup instead
+fn load_totp_encryption_key() -> [u8; 32] {
+    let key = env::var("TOTP_ENCRYPTION_KEY").expect("TOTP_ENCRYPTION_KEY must be set");
+    let key = hex::decode(key).expect("TOTP_ENCRYPTION_KEY must be hex encoded");
+
+    <[u8; 32]>::try_from(key).expect("TOTP_ENCRYPTION_KEY must be 32 bytes long")
+}
@@ -1,7 +1,11 @@
 use crate::{
     services::database::DatabaseLayer,
     utils::schemas::{
-        EMAIL_VERIFICATION_SCHEMA, PASSWORD_RESET_REQUEST_SCHEMA, SESSION_SCHEMA, USER_SCHEMA,
+        ACCOUNT_DELETION_SCHEMA, ACCOUNT_UNLOCK_SCHEMA, DATA_EXPORT_SCHEMA, EMAIL_OUTBOX_SCHEMA,
+        EMAIL_VERIFICATION_DISPATCH_SCHEMA, EMAIL_VERIFICATION_SCHEMA, FAILED_SIGNIN_SCHEMA,
+        MAGIC_LINK_DISPATCH_SCHEMA, MAGIC_LINK_SCHEMA, PASSKEY_SCHEMA,
+        PASSWORD_RESET_REQUEST_SCHEMA, RECOVERY_CODE_SCHEMA, SESSION_SCHEMA, USER_SCHEMA,
+        WEBAUTHN_CHALLENGE_SCHEMA,
     },
 };
 
@@ -15,12 +19,26 @@ pub async fn setup_database() -> surrealdb::Result<DatabaseLayer> {
     )
     .await?;
 
+    // Has to run before the schemas, the unique email index can't be defined over duplicates
+    db_layer.query().user.normalize_emails().await?;
+
     db_layer
         .initialize_schemas(vec![
             USER_SCHEMA,
             EMAIL_VERIFICATION_SCHEMA,
+            EMAIL_VERIFICATION_DISPATCH_SCHEMA,
+            MAGIC_LINK_SCHEMA,
+            MAGIC_LINK_DISPATCH_SCHEMA,
             SESSION_SCHEMA,
             PASSWORD_RESET_REQUEST_SCHEMA,
+            RECOVERY_CODE_SCHEMA,
+            PASSKEY_SCHEMA,
+            WEBAUTHN_CHALLENGE_SCHEMA,
+            FAILED_SIGNIN_SCHEMA,
+            ACCOUNT_UNLOCK_SCHEMA,
+            ACCOUNT_DELETION_SCHEMA,
+            DATA_EXPORT_SCHEMA,
+            EMAIL_OUTBOX_SCHEMA,
         ])
         .await?;
 
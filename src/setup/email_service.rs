@@ -1,12 +1,85 @@
-use crate::services::email::EmailLayer;
-use std::env;
+use std::{env, path::PathBuf, sync::Arc};
+
+use crate::services::{
+    database::DatabaseLayer,
+    email::{
+        EmailBranding, EmailLayer, EmailTemplates, EmailTransport, FileTransport, MemoryTransport,
+        ResendTransport, SmtpTransport,
+    },
+};
+
+pub fn setup_email_service(database_layer: DatabaseLayer) -> EmailLayer {
+    let domain = env::var("EMAIL_DOMAIN").unwrap_or_else(|_| String::from("blazar.lol"));
+
+    // Captured emails can be read at /dev/mailbox, so the flows that need them work locally
+    // without an email provider. Anyone could read every code and link sent through the mailbox,
+    // so it's refused outside of development
+    if env::var("EMAIL_TRANSPORT").as_deref() == Ok("memory") {
+        if env::var("APP_ENV").as_deref() != Ok("development") {
+            panic!("EMAIL_TRANSPORT=memory is only allowed with APP_ENV=development");
+        }
+
+        let mailbox = MemoryTransport::new();
+        println!("Emails are kept in memory, the development mailbox is served at /dev/mailbox");
+
+        return EmailLayer::new(
+            Arc::new(mailbox.clone()),
+            setup_email_templates(),
+            database_layer,
+            domain,
+        )
+        .with_mailbox(mailbox);
+    }
 
-pub fn setup_email_service() -> EmailLayer {
     EmailLayer::new(
-        env::var("RESEND_API_KEY").unwrap_or_else(|_| {
-            println!("Resend API key error");
-            String::new()
-        }),
-        String::from("blazar.lol"),
+        setup_email_transport(),
+        setup_email_templates(),
+        database_layer,
+        domain,
+    )
+}
+
+// Copy can be edited without a release by pointing EMAIL_TEMPLATE_DIR at a directory with
+// the templates to override
+fn setup_email_templates() -> EmailTemplates {
+    let branding = EmailBranding {
+        name: env::var("EMAIL_BRAND_NAME").unwrap_or_else(|_| String::from("Orvane")),
+        primary_color: env::var("EMAIL_BRAND_COLOR").unwrap_or_else(|_| String::from("#4f46e5")),
+        logo_url: env::var("EMAIL_BRAND_LOGO_URL").ok(),
+        support_email: env::var("EMAIL_SUPPORT_ADDRESS").ok(),
+    };
+
+    EmailTemplates::new(
+        branding,
+        env::var("EMAIL_TEMPLATE_DIR").ok().map(PathBuf::from),
     )
 }
+
+// Resend is used in production, the other transports let the API run without a Resend key
+fn setup_email_transport() -> Arc<dyn EmailTransport> {
+    match env::var("EMAIL_TRANSPORT").as_deref() {
+        Ok("smtp") => {
+            let host = env::var("SMTP_HOST").unwrap_or_else(|_| String::from("localhost"));
+            let port = env::var("SMTP_PORT")
+                .ok()
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(1025);
+            let credentials = match (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD")) {
+                (Ok(username), Ok(password)) => Some((username, password)),
+                _ => None,
+            };
+
+            Arc::new(
+                SmtpTransport::new(&host, port, credentials)
+                    .expect("SMTP transport couldn't be set up"),
+            )
+        }
+        Ok("file") => Arc::new(FileTransport::new(PathBuf::from(
+            env::var("EMAIL_FILE_DIR").unwrap_or_else(|_| String::from("emails")),
+        ))),
+        _ => Arc::new(ResendTransport::new(
+            &env::var("RESEND_API_KEY")
+                .expect("RESEND_API_KEY must be set when EMAIL_TRANSPORT is resend"),
+        )),
+    }
+}
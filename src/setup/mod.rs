@@ -1,7 +1,11 @@
+mod breached_password;
+mod config;
 mod database;
 mod email_service;
 mod router;
 
+pub use breached_password::setup_breached_password_screening;
+pub use config::{setup_config, AppConfig, SigninLockoutPolicy, VerificationAccess};
 pub use database::setup_database;
 pub use email_service::setup_email_service;
 pub use router::{setup_api_router, AppState};
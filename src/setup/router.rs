@@ -1,20 +1,35 @@
 use crate::{
     routes,
-    services::{database::DatabaseLayer, email::EmailLayer},
+    services::{
+        breached_password::BreachedPasswordScreening, database::DatabaseLayer, email::EmailLayer,
+        i18n::locale_middleware,
+    },
 };
-use axum::{Extension, Router};
+use axum::{middleware, Extension, Router};
 use tokio::net::TcpListener;
 
+use super::AppConfig;
+
 #[derive(Clone)]
-pub struct AppState {}
+pub struct AppState {
+    pub config: AppConfig,
+    pub breached_passwords: BreachedPasswordScreening,
+}
 
 pub async fn setup_api_router(
+    config: AppConfig,
+    breached_passwords: BreachedPasswordScreening,
     database_layer: DatabaseLayer,
     email_layer: EmailLayer,
 ) -> surrealdb::Result<(Router, TcpListener)> {
-    let shared_state = AppState {};
+    let shared_state = AppState {
+        config,
+        breached_passwords,
+    };
 
-    let app = routes::main_router()
+    // Layers added last run first, the locale middleware needs the database extension
+    let app = routes::main_router(email_layer.mailbox().is_some())
+        .layer(middleware::from_fn(locale_middleware))
         .layer(Extension(database_layer))
         .layer(Extension(email_layer))
         .with_state(shared_state);
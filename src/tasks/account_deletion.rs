@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use crate::services::{database::DatabaseLayer, email::EmailLayer, i18n::with_locale};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+// Permanently removes accounts whose deletion grace period is over
+pub async fn run(database_layer: DatabaseLayer, email_layer: EmailLayer) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let due_deletions = match database_layer.query().account_deletion.list_due().await {
+            Ok(due_deletions) => due_deletions,
+            Err(err) => {
+                println!("Failed to list due account deletions: {}", err);
+                continue;
+            }
+        };
+
+        for account_deletion in due_deletions {
+            let user = match database_layer
+                .query()
+                .user
+                .get_by_id(account_deletion.user.clone())
+                .await
+            {
+                Ok(user) => user,
+                Err(err) => {
+                    println!("Failed to retrieve user scheduled for deletion: {}", err);
+                    continue;
+                }
+            };
+
+            match database_layer
+                .query()
+                .user
+                .delete_with_related(user.id.clone())
+                .await
+            {
+                Ok(true) => println!("User {} deleted successfully!", user.id),
+                Ok(false) => {
+                    println!("Deletion of user {} was cancelled in the meantime", user.id);
+                    continue;
+                }
+                Err(err) => {
+                    println!("Failed to delete user {}: {}", user.id, err);
+                    continue;
+                }
+            }
+
+            let locale = user.preferred_locale();
+
+            if let Err(err) =
+                with_locale(locale, email_layer.send_account_deleted(user.email)).await
+            {
+                println!("Failed to send account deletion confirmation: {}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use crate::services::database::DatabaseLayer;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+// Removes data export archives whose download link has expired, along with expired WebAuthn
+// challenges
+pub async fn run(database_layer: DatabaseLayer) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = database_layer.query().data_export.remove_expired().await {
+            println!("Failed to remove expired data exports: {}", err);
+        }
+
+        if let Err(err) = database_layer
+            .query()
+            .webauthn_challenge
+            .remove_expired()
+            .await
+        {
+            println!("Failed to remove expired WebAuthn challenges: {}", err);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::services::{
+    database::DatabaseLayer,
+    email::{EmailError, EmailLayer},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Emails are leased one at a time, a delivery is given up before its lease runs out so another
+// worker can't claim the email while it's still being sent
+const LEASE: chrono::Duration = chrono::Duration::minutes(2);
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_ATTEMPTS: i64 = 10;
+const BASE_BACKOFF_SECONDS: i64 = 30;
+const MAX_BACKOFF_SECONDS: i64 = 60 * 60;
+const SENT_RETENTION: chrono::Duration = chrono::Duration::days(7);
+
+// Delivers the emails queued in the outbox, failed deliveries are retried with exponential
+// backoff until they're dead lettered after `MAX_ATTEMPTS`
+pub async fn run(database_layer: DatabaseLayer, email_layer: EmailLayer) {
+    let mut cleanup_interval = tokio::time::interval(CLEANUP_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            _ = email_layer.outbox_notified() => {}
+            _ = cleanup_interval.tick() => {
+                if let Err(err) = database_layer
+                    .query()
+                    .email_outbox
+                    .remove_sent_before(Utc::now() - SENT_RETENTION)
+                    .await
+                {
+                    println!("Failed to remove sent emails from the outbox: {}", err);
+                }
+                continue;
+            }
+        }
+
+        let due = match database_layer.query().email_outbox.list_due().await {
+            Ok(due) => due,
+            Err(err) => {
+                println!("Failed to list due emails in the outbox: {}", err);
+                continue;
+            }
+        };
+
+        for email_id in due {
+            let email = match database_layer
+                .query()
+                .email_outbox
+                .claim(email_id, Utc::now() + LEASE)
+                .await
+            {
+                Ok(Some(email)) => email,
+                Ok(None) => continue,
+                Err(err) => {
+                    println!("Failed to claim email from the outbox: {}", err);
+                    continue;
+                }
+            };
+
+            let message = email.message();
+            let delivery = tokio::time::timeout(DELIVERY_TIMEOUT, email_layer.deliver(&message));
+
+            match delivery.await.unwrap_or(Err(EmailError::Timeout)) {
+                Ok(_) => {
+                    if let Err(err) = database_layer
+                        .query()
+                        .email_outbox
+                        .mark_sent(email.id.clone())
+                        .await
+                    {
+                        println!("Failed to mark outbox email as sent: {}", err);
+                    }
+                }
+                Err(err) => {
+                    let attempts = email.attempts + 1;
+                    let next_attempt_at = (attempts < MAX_ATTEMPTS)
+                        .then(|| Utc::now() + chrono::Duration::seconds(backoff_seconds(attempts)));
+
+                    match next_attempt_at {
+                        Some(_) => println!(
+                            "Failed to deliver email {} (attempt {}): {}",
+                            email.id, attempts, err
+                        ),
+                        None => println!(
+                            "Email {} dead lettered after {} attempts: {}",
+                            email.id, attempts, err
+                        ),
+                    }
+
+                    if let Err(err) = database_layer
+                        .query()
+                        .email_outbox
+                        .record_failure(email.id.clone(), err.to_string(), next_attempt_at)
+                        .await
+                    {
+                        println!("Failed to record outbox delivery failure: {}", err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 30 seconds after the first failure, doubling with every attempt up to an hour
+fn backoff_seconds(attempts: i64) -> i64 {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+
+    (BASE_BACKOFF_SECONDS * 2_i64.pow(exponent)).min(MAX_BACKOFF_SECONDS)
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::services::database::DatabaseLayer;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Well past any attempt window, older failures don't count towards a lock anymore
+const RETENTION: chrono::Duration = chrono::Duration::days(1);
+
+// Removes old failed signins, including the ones for unknown emails that no signin ever clears
+pub async fn run(database_layer: DatabaseLayer) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = database_layer
+            .query()
+            .failed_signin
+            .remove_before(Utc::now() - RETENTION)
+            .await
+        {
+            println!("Failed to remove old failed signins: {}", err);
+        }
+    }
+}
@@ -0,0 +1,22 @@
+mod account_deletion;
+mod data_export;
+mod email_outbox;
+mod failed_signin_cleanup;
+mod verification_email_retry;
+
+use crate::services::{database::DatabaseLayer, email::EmailLayer};
+
+// Spawns the periodic jobs that run next to the API server
+pub fn spawn_background_tasks(database_layer: DatabaseLayer, email_layer: EmailLayer) {
+    tokio::spawn(data_export::run(database_layer.clone()));
+    tokio::spawn(failed_signin_cleanup::run(database_layer.clone()));
+    tokio::spawn(account_deletion::run(
+        database_layer.clone(),
+        email_layer.clone(),
+    ));
+    tokio::spawn(email_outbox::run(
+        database_layer.clone(),
+        email_layer.clone(),
+    ));
+    tokio::spawn(verification_email_retry::run(database_layer, email_layer));
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::{
+    services::{database::DatabaseLayer, email::EmailLayer, i18n::with_locale},
+    utils::{crypto::hash_string, random::generate_random_code},
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_ATTEMPTS: i64 = 5;
+// Matches the resend limit window, older sends are neither counted nor retried
+const DISPATCH_RETENTION: chrono::Duration = chrono::Duration::days(1);
+
+// Resends verification emails that couldn't be queued in the outbox, once per user no matter how
+// many sends failed. Each retry comes with a fresh code since only the hash of the original one
+// is stored
+pub async fn run(database_layer: DatabaseLayer, email_layer: EmailLayer) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = database_layer
+            .query()
+            .email_verification
+            .remove_dispatches_before(Utc::now() - DISPATCH_RETENTION)
+            .await
+        {
+            println!(
+                "Failed to remove old verification email dispatches: {}",
+                err
+            );
+        }
+
+        let undelivered = match database_layer
+            .query()
+            .email_verification
+            .list_undelivered_dispatches(MAX_ATTEMPTS)
+            .await
+        {
+            Ok(undelivered) => undelivered,
+            Err(err) => {
+                println!("Failed to list undelivered verification emails: {}", err);
+                continue;
+            }
+        };
+
+        // Users can have several failed sends, one retry covers all of them
+        let mut user_ids = Vec::new();
+
+        for dispatch in undelivered {
+            if !user_ids.contains(&dispatch.user) {
+                user_ids.push(dispatch.user);
+            }
+        }
+
+        for user_id in user_ids {
+            let user = match database_layer.query().user.get_by_id(user_id).await {
+                Ok(user) => user,
+                Err(err) => {
+                    println!(
+                        "Failed to retrieve user for verification email retry: {}",
+                        err
+                    );
+                    continue;
+                }
+            };
+
+            let verification_code = generate_random_code(6);
+            let verification_code_hash = hash_string(verification_code.clone());
+
+            let email_verification = match database_layer
+                .query()
+                .email_verification
+                .create(verification_code_hash, user.id.clone())
+                .await
+            {
+                Ok(email_verification) => email_verification,
+                Err(err) => {
+                    println!("Failed to create email verification for retry: {}", err);
+                    continue;
+                }
+            };
+
+            let token_hash = hash_string(email_verification.id.id.clone().to_string());
+
+            let locale = user.preferred_locale();
+            let send_result = with_locale(
+                locale,
+                email_layer.send_email_verification(user.email, verification_code, token_hash),
+            )
+            .await;
+
+            match send_result {
+                Ok(_) => {
+                    if let Err(err) = database_layer
+                        .query()
+                        .email_verification
+                        .record_dispatch(user.id.clone(), true)
+                        .await
+                    {
+                        println!("Failed to record verification email dispatch: {}", err);
+                    }
+                    println!(
+                        "Verification email for user {} resent successfully!",
+                        user.id
+                    );
+                }
+                Err(err) => {
+                    println!("Failed to resend verification email: {}", err);
+
+                    if let Err(err) = database_layer
+                        .query()
+                        .email_verification
+                        .increment_dispatch_attempts(user.id)
+                        .await
+                    {
+                        println!("Failed to update verification email dispatch: {}", err);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,6 +2,8 @@ use axum_extra::extract::cookie::{Cookie, SameSite};
 use chrono::{Duration, Utc};
 use cookie::time::OffsetDateTime;
 
+pub const SESSION_COOKIE_NAME: &str = "session_id";
+
 pub fn set_session_cookie(session_token: String, authorized: bool) -> Cookie<'static> {
     let now = Utc::now();
 
@@ -13,7 +15,7 @@ pub fn set_session_cookie(session_token: String, authorized: bool) -> Cookie<'st
 
     let expiration_time = OffsetDateTime::from_unix_timestamp(expiration_time.timestamp()).unwrap();
 
-    Cookie::build(("session_id", session_token))
+    Cookie::build((SESSION_COOKIE_NAME, session_token))
         .path("/")
         .same_site(SameSite::Lax)
         .secure(true)
@@ -21,3 +23,13 @@ pub fn set_session_cookie(session_token: String, authorized: bool) -> Cookie<'st
         .expires(expiration_time)
         .build()
 }
+
+pub fn remove_session_cookie() -> Cookie<'static> {
+    Cookie::build((SESSION_COOKIE_NAME, ""))
+        .path("/")
+        .same_site(SameSite::Lax)
+        .secure(true)
+        .http_only(true)
+        .expires(OffsetDateTime::UNIX_EPOCH)
+        .build()
+}
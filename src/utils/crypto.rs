@@ -1,3 +1,7 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit},
+    Aes256Gcm, Nonce,
+};
 use argon2::{password_hash::SaltString, Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use data_encoding::BASE32_NOPAD;
 use rand::{rngs::OsRng, RngCore};
@@ -18,10 +22,11 @@ pub async fn verify_password_hash(
     hash: String,
 ) -> Result<bool, argon2::password_hash::Error> {
     let argon2 = Argon2::default();
-    let hash = PasswordHash::new(hash.as_str()).unwrap();
+    let hash = PasswordHash::new(hash.as_str())?;
 
     match argon2.verify_password(password.as_bytes(), &hash) {
         Ok(_) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
         Err(e) => Err(e),
     }
 }
@@ -62,3 +67,30 @@ pub fn hash_token(token: &str) -> String {
     hasher.update(token);
     hex::encode(hasher.finalize())
 }
+
+// Encrypts a secret with AES-256-GCM, the output is the hex encoded nonce followed by the ciphertext
+pub fn encrypt_secret(secret: &[u8], key: &[u8; 32]) -> Result<String, aes_gcm::Error> {
+    let cipher = Aes256Gcm::new(key.into());
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher.encrypt(&nonce, secret)?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend(ciphertext);
+
+    Ok(hex::encode(payload))
+}
+
+pub fn decrypt_secret(encrypted_secret: &str, key: &[u8; 32]) -> Result<Vec<u8>, aes_gcm::Error> {
+    let cipher = Aes256Gcm::new(key.into());
+
+    let payload = hex::decode(encrypted_secret).map_err(|_| aes_gcm::Error)?;
+
+    if payload.len() < 12 {
+        return Err(aes_gcm::Error);
+    }
+
+    let (nonce, ciphertext) = payload.split_at(12);
+
+    cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+}
@@ -0,0 +1,43 @@
+// Builds a human readable label such as "Firefox on Windows" from a user agent string
+pub fn describe_device(user_agent: Option<&str>) -> String {
+    let user_agent = match user_agent {
+        Some(user_agent) if !user_agent.trim().is_empty() => user_agent,
+        _ => return String::from("Unknown device"),
+    };
+
+    // Order matters, most browsers include the tokens of the ones they are based on
+    let browser = if user_agent.contains("Edg/") {
+        Some("Edge")
+    } else if user_agent.contains("OPR/") || user_agent.contains("Opera") {
+        Some("Opera")
+    } else if user_agent.contains("Firefox/") {
+        Some("Firefox")
+    } else if user_agent.contains("Chrome/") || user_agent.contains("CriOS/") {
+        Some("Chrome")
+    } else if user_agent.contains("Safari/") {
+        Some("Safari")
+    } else {
+        None
+    };
+
+    let os = if user_agent.contains("iPhone") || user_agent.contains("iPad") {
+        Some("iOS")
+    } else if user_agent.contains("Android") {
+        Some("Android")
+    } else if user_agent.contains("Windows") {
+        Some("Windows")
+    } else if user_agent.contains("Mac OS X") || user_agent.contains("Macintosh") {
+        Some("macOS")
+    } else if user_agent.contains("Linux") {
+        Some("Linux")
+    } else {
+        None
+    };
+
+    match (browser, os) {
+        (Some(browser), Some(os)) => format!("{} on {}", browser, os),
+        (Some(browser), None) => browser.to_string(),
+        (None, Some(os)) => format!("Unknown browser on {}", os),
+        (None, None) => String::from("Unknown device"),
+    }
+}
@@ -1,5 +1,8 @@
 pub mod cookies;
 pub mod crypto;
+pub mod device;
 pub mod random;
 pub mod schemas;
+pub mod totp;
 pub mod validation;
+pub mod webauthn;
@@ -1,5 +1,7 @@
 use rand::{thread_rng, Rng};
 
+use super::crypto::generate_token;
+
 pub fn generate_random_code(length: usize) -> String {
     let mut rng = thread_rng();
 
@@ -9,3 +11,13 @@ pub fn generate_random_code(length: usize) -> String {
 
     code
 }
+
+// Recovery codes are formatted as two groups of 5 base32 characters, e.g. "k3f9a-x2mq7"
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|_| {
+            let token = generate_token();
+            format!("{}-{}", &token[0..5], &token[5..10])
+        })
+        .collect()
+}
@@ -5,18 +5,61 @@ pub const USER_SCHEMA: &str = r#"
     DEFINE FIELD email_verified ON TABLE user TYPE bool DEFAULT false;
     DEFINE FIELD password_hash ON TABLE user TYPE string;
     DEFINE FIELD created_at ON TABLE user TYPE datetime;
+    DEFINE FIELD totp_secret ON TABLE user TYPE option<string>;
+    DEFINE FIELD totp_enabled ON TABLE user TYPE bool DEFAULT false;
+    DEFINE FIELD totp_last_step ON TABLE user TYPE option<int>;
+    DEFINE FIELD totp_setup_attempts ON TABLE user TYPE int DEFAULT 0;
+    DEFINE FIELD locked_until ON TABLE user TYPE option<datetime>;
+    DEFINE FIELD lock_count ON TABLE user TYPE int DEFAULT 0;
+    DEFINE FIELD deletion_scheduled_at ON TABLE user TYPE option<datetime>;
+    DEFINE FIELD locale ON TABLE user TYPE option<string>;
+
+    DEFINE INDEX user_email ON TABLE user COLUMNS email UNIQUE;
 "#;
 
 pub const EMAIL_VERIFICATION_SCHEMA: &str = r#"
     DEFINE TABLE email_verification SCHEMAFULL;
 
     DEFINE FIELD code ON TABLE email_verification TYPE string;
+    DEFINE FIELD attempts ON TABLE email_verification TYPE int DEFAULT 0;
+    DEFINE FIELD purpose ON TABLE email_verification TYPE string DEFAULT "signup";
+    DEFINE FIELD new_email ON TABLE email_verification TYPE option<string>;
+    DEFINE FIELD cancel_token_hash ON TABLE email_verification TYPE option<string>;
     DEFINE FIELD created_at ON TABLE email_verification TYPE datetime;
     DEFINE FIELD expires_at ON TABLE email_verification TYPE datetime;
 
     DEFINE FIELD user ON TABLE email_verification TYPE record<user>;
 "#;
 
+pub const MAGIC_LINK_SCHEMA: &str = r#"
+    DEFINE TABLE magic_link SCHEMAFULL;
+
+    DEFINE FIELD code ON TABLE magic_link TYPE string;
+    DEFINE FIELD attempts ON TABLE magic_link TYPE int DEFAULT 0;
+    DEFINE FIELD created_at ON TABLE magic_link TYPE datetime;
+    DEFINE FIELD expires_at ON TABLE magic_link TYPE datetime;
+
+    DEFINE FIELD user ON TABLE magic_link TYPE record<user>;
+"#;
+
+pub const EMAIL_VERIFICATION_DISPATCH_SCHEMA: &str = r#"
+    DEFINE TABLE email_verification_dispatch SCHEMAFULL;
+
+    DEFINE FIELD delivered ON TABLE email_verification_dispatch TYPE bool DEFAULT true;
+    DEFINE FIELD attempts ON TABLE email_verification_dispatch TYPE int DEFAULT 0;
+    DEFINE FIELD created_at ON TABLE email_verification_dispatch TYPE datetime;
+
+    DEFINE FIELD user ON TABLE email_verification_dispatch TYPE record<user>;
+"#;
+
+pub const MAGIC_LINK_DISPATCH_SCHEMA: &str = r#"
+    DEFINE TABLE magic_link_dispatch SCHEMAFULL;
+
+    DEFINE FIELD created_at ON TABLE magic_link_dispatch TYPE datetime;
+
+    DEFINE FIELD user ON TABLE magic_link_dispatch TYPE record<user>;
+"#;
+
 pub const SESSION_SCHEMA: &str = r#"
     DEFINE TABLE session SCHEMAFULL;
 
@@ -24,6 +67,10 @@ pub const SESSION_SCHEMA: &str = r#"
     DEFINE FIELD created_at ON TABLE session TYPE datetime;
     DEFINE FIELD expires_at ON TABLE session TYPE datetime;
     DEFINE FIELD last_accessed_at ON TABLE session TYPE datetime;
+    DEFINE FIELD user_agent ON TABLE session TYPE option<string>;
+    DEFINE FIELD ip_address ON TABLE session TYPE option<string>;
+    DEFINE FIELD device_label ON TABLE session TYPE option<string>;
+    DEFINE FIELD second_factor_attempts ON TABLE session TYPE int DEFAULT 0;
 
     DEFINE FIELD user ON TABLE session TYPE record<user>;
 "#;
@@ -37,4 +84,102 @@ pub const PASSWORD_RESET_REQUEST_SCHEMA: &str = r#"
     DEFINE FIELD user ON TABLE password_reset_request TYPE record<user>;
 "#;
 
+pub const RECOVERY_CODE_SCHEMA: &str = r#"
+    DEFINE TABLE recovery_code SCHEMAFULL;
+
+    DEFINE FIELD code_hash ON TABLE recovery_code TYPE string;
+    DEFINE FIELD created_at ON TABLE recovery_code TYPE datetime;
+
+    DEFINE FIELD user ON TABLE recovery_code TYPE record<user>;
+"#;
+
+pub const PASSKEY_SCHEMA: &str = r#"
+    DEFINE TABLE passkey SCHEMAFULL;
+
+    DEFINE FIELD credential_id ON TABLE passkey TYPE string;
+    DEFINE FIELD public_key ON TABLE passkey TYPE string;
+    DEFINE FIELD sign_count ON TABLE passkey TYPE int;
+    DEFINE FIELD name ON TABLE passkey TYPE option<string>;
+    DEFINE FIELD created_at ON TABLE passkey TYPE datetime;
+    DEFINE FIELD last_used_at ON TABLE passkey TYPE option<datetime>;
+
+    DEFINE FIELD user ON TABLE passkey TYPE record<user>;
+
+    DEFINE INDEX passkey_credential_id ON TABLE passkey COLUMNS credential_id UNIQUE;
+"#;
+
+pub const WEBAUTHN_CHALLENGE_SCHEMA: &str = r#"
+    DEFINE TABLE webauthn_challenge SCHEMAFULL;
+
+    DEFINE FIELD challenge ON TABLE webauthn_challenge TYPE string;
+    DEFINE FIELD purpose ON TABLE webauthn_challenge TYPE string;
+    DEFINE FIELD created_at ON TABLE webauthn_challenge TYPE datetime;
+    DEFINE FIELD expires_at ON TABLE webauthn_challenge TYPE datetime;
+
+    DEFINE FIELD user ON TABLE webauthn_challenge TYPE option<record<user>>;
+"#;
+
+pub const FAILED_SIGNIN_SCHEMA: &str = r#"
+    DEFINE TABLE failed_signin SCHEMAFULL;
+
+    DEFINE FIELD email ON TABLE failed_signin TYPE string;
+    DEFINE FIELD ip_address ON TABLE failed_signin TYPE option<string>;
+    DEFINE FIELD created_at ON TABLE failed_signin TYPE datetime;
+
+    DEFINE FIELD user ON TABLE failed_signin TYPE option<record<user>>;
+
+    DEFINE INDEX failed_signin_user ON TABLE failed_signin COLUMNS user;
+    DEFINE INDEX failed_signin_ip_address ON TABLE failed_signin COLUMNS ip_address;
+"#;
+
+pub const ACCOUNT_UNLOCK_SCHEMA: &str = r#"
+    DEFINE TABLE account_unlock SCHEMAFULL;
+
+    DEFINE FIELD created_at ON TABLE account_unlock TYPE datetime;
+    DEFINE FIELD expires_at ON TABLE account_unlock TYPE datetime;
+
+    DEFINE FIELD user ON TABLE account_unlock TYPE record<user>;
+"#;
+
+pub const ACCOUNT_DELETION_SCHEMA: &str = r#"
+    DEFINE TABLE account_deletion SCHEMAFULL;
+
+    DEFINE FIELD created_at ON TABLE account_deletion TYPE datetime;
+    DEFINE FIELD scheduled_at ON TABLE account_deletion TYPE datetime;
+
+    DEFINE FIELD user ON TABLE account_deletion TYPE record<user>;
+
+    DEFINE INDEX account_deletion_scheduled_at ON TABLE account_deletion COLUMNS scheduled_at;
+"#;
+
+pub const DATA_EXPORT_SCHEMA: &str = r#"
+    DEFINE TABLE data_export SCHEMAFULL;
+
+    DEFINE FIELD archive ON TABLE data_export TYPE string;
+    DEFINE FIELD created_at ON TABLE data_export TYPE datetime;
+    DEFINE FIELD expires_at ON TABLE data_export TYPE datetime;
+
+    DEFINE FIELD user ON TABLE data_export TYPE record<user>;
+"#;
+
+pub const EMAIL_OUTBOX_SCHEMA: &str = r#"
+    DEFINE TABLE email_outbox SCHEMAFULL;
+
+    DEFINE FIELD sender ON TABLE email_outbox TYPE string;
+    DEFINE FIELD recipient ON TABLE email_outbox TYPE string;
+    DEFINE FIELD subject ON TABLE email_outbox TYPE string;
+    DEFINE FIELD html ON TABLE email_outbox TYPE string;
+    DEFINE FIELD text ON TABLE email_outbox TYPE option<string>;
+    DEFINE FIELD status ON TABLE email_outbox TYPE string DEFAULT "pending";
+    DEFINE FIELD attempts ON TABLE email_outbox TYPE int DEFAULT 0;
+    DEFINE FIELD last_error ON TABLE email_outbox TYPE option<string>;
+    DEFINE FIELD created_at ON TABLE email_outbox TYPE datetime;
+    DEFINE FIELD next_attempt_at ON TABLE email_outbox TYPE datetime;
+    DEFINE FIELD sent_at ON TABLE email_outbox TYPE option<datetime>;
+
+    DEFINE FIELD user ON TABLE email_outbox TYPE option<record<user>>;
+
+    DEFINE INDEX email_outbox_due ON TABLE email_outbox COLUMNS status, next_attempt_at;
+"#;
+
 // TODO: Create schemas for relation tables
@@ -0,0 +1,42 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+use totp_rs::{Algorithm, TOTP};
+
+const TOTP_ISSUER: &str = "Orvane";
+
+pub fn generate_totp_secret() -> Vec<u8> {
+    let mut secret = [0u8; 20];
+
+    rand::thread_rng().fill_bytes(&mut secret);
+
+    secret.to_vec()
+}
+
+// RFC 6238 defaults: SHA-1, 6 digits, 30 second step, one step of clock skew allowed
+pub fn build_totp(secret: Vec<u8>, account_name: String) -> TOTP {
+    TOTP::new_unchecked(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        secret,
+        Some(String::from(TOTP_ISSUER)),
+        account_name,
+    )
+}
+
+// Returns the time step the code was generated for, storing the last accepted step lets callers
+// reject codes that were already used
+pub fn verify_totp_code(secret: Vec<u8>, code: &str) -> Option<i64> {
+    let mut totp = build_totp(secret, String::new());
+    let skew = totp.skew as u64;
+    let current_step = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() / totp.step;
+
+    // Every step is checked on its own to find out which one matched
+    totp.skew = 0;
+
+    (current_step.saturating_sub(skew)..=current_step + skew)
+        .find(|step| totp.check(code, step * totp.step))
+        .map(|step| step as i64)
+}
@@ -1,29 +1,313 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet};
 
 use lazy_static::lazy_static;
 use regex::Regex;
-use validator::ValidationError;
+use validator::{ValidationError, ValidationErrors};
+
+use crate::services::i18n::{translate, translate_with_args, FluentArgs};
 
 lazy_static! {
     static ref NUMERIC_ONLY: Regex = Regex::new(r"^\d+$").unwrap();
+    // Bundled list of frequently leaked passwords, compared in lowercase
+    static ref COMMON_PASSWORDS: HashSet<&'static str> = include_str!("data/common_passwords.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    // Bounds the common prefix lookup in `estimate_guesses_bits`
+    static ref LONGEST_COMMON_PASSWORD: usize = COMMON_PASSWORDS
+        .iter()
+        .map(|password| password.chars().count())
+        .max()
+        .unwrap_or(0);
 }
 
-pub fn validate_email_verification_code_length(code: &str) -> Result<(), ValidationError> {
+pub fn validate_numeric_code_length(code: &str) -> Result<(), ValidationError> {
     if code.len() != 6 {
         let mut error = ValidationError::new("invalid_length");
-        error.message = Some(Cow::from("The code must be exactly 6 digits long"));
+        error.message = Some(Cow::from(translate("validation-code-length")));
         return Err(error);
     }
 
     Ok(())
 }
 
-pub fn validate_email_verification_code_format(code: &str) -> Result<(), ValidationError> {
+pub fn validate_numeric_code_format(code: &str) -> Result<(), ValidationError> {
     if !NUMERIC_ONLY.is_match(code) {
         let mut error = ValidationError::new("invalid_format");
-        error.message = Some(Cow::from("The code must contain only numbers"));
+        error.message = Some(Cow::from(translate("validation-code-format")));
         return Err(error);
     }
 
     Ok(())
 }
+
+// Emails are stored and looked up trimmed and lowercased, so the same address can't be
+// registered twice with different casing
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+// Password policy
+
+#[derive(Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    // Minimum strength score from 0 (trivial) to 4 (very strong), see `estimate_password_score`
+    pub min_score: u8,
+}
+
+impl PasswordPolicy {
+    // Collects every rule the password breaks under the `password` field
+    pub fn check(&self, password: &str, email: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let length = password.chars().count();
+
+        if length < self.min_length {
+            errors.add(
+                "password",
+                password_error(
+                    "too_short",
+                    translate_with_args(
+                        "validation-password-too-short",
+                        &FluentArgs::from_iter([("min", self.min_length)]),
+                    ),
+                ),
+            );
+        }
+
+        // Nothing else is checked for passwords over the limit, scoring them would only burn CPU
+        if length > self.max_length {
+            errors.add(
+                "password",
+                password_error(
+                    "too_long",
+                    translate_with_args(
+                        "validation-password-too-long",
+                        &FluentArgs::from_iter([("max", self.max_length)]),
+                    ),
+                ),
+            );
+
+            return Err(errors);
+        }
+
+        let normalized = password.to_lowercase();
+
+        if COMMON_PASSWORDS.contains(normalized.as_str()) {
+            errors.add(
+                "password",
+                password_error("common_password", translate("validation-password-common")),
+            );
+        }
+
+        if contains_email(&normalized, email) {
+            errors.add(
+                "password",
+                password_error(
+                    "contains_email",
+                    translate("validation-password-contains-email"),
+                ),
+            );
+        }
+
+        if estimate_password_score(password) < self.min_score {
+            errors.add(
+                "password",
+                password_error("too_weak", translate("validation-password-too-weak")),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn password_error(code: &'static str, message: String) -> ValidationError {
+    let mut error = ValidationError::new(code);
+    error.message = Some(Cow::from(message));
+    error
+}
+
+fn contains_email(normalized_password: &str, email: &str) -> bool {
+    let email = email.to_lowercase();
+    let local_part = email.split('@').next().unwrap_or_default();
+
+    normalized_password.contains(&email)
+        || (local_part.chars().count() >= 3 && normalized_password.contains(local_part))
+}
+
+// Rough zxcvbn-style estimate, maps the estimated number of guesses to a score from 0 to 4
+pub fn estimate_password_score(password: &str) -> u8 {
+    let guesses_log10 = estimate_guesses_bits(password) * 2f64.log10();
+
+    match guesses_log10 {
+        g if g < 3.0 => 0,
+        g if g < 6.0 => 1,
+        g if g < 8.0 => 2,
+        g if g < 10.0 => 3,
+        _ => 4,
+    }
+}
+
+fn estimate_guesses_bits(password: &str) -> f64 {
+    let chars: Vec<char> = password.chars().collect();
+
+    // A common password followed by a few characters like "password2024!" is guessed early, so
+    // the longest common prefix only counts as a lookup in the list
+    let mut prefix = String::new();
+    let mut common_prefix_length = 0;
+    for (index, c) in chars.iter().take(*LONGEST_COMMON_PASSWORD).enumerate() {
+        prefix.extend(c.to_lowercase());
+
+        if index + 1 >= 4 && COMMON_PASSWORDS.contains(prefix.as_str()) {
+            common_prefix_length = index + 1;
+        }
+    }
+
+    let dictionary_bits = if common_prefix_length > 0 {
+        (COMMON_PASSWORDS.len() as f64 * 2.0).log2()
+    } else {
+        0.0
+    };
+
+    let remainder = &chars[common_prefix_length..];
+    let charset_bits = charset_size(remainder).log2();
+
+    // Years ("1999", "2024") are guessed as a whole, repeated characters ("aaaa") and sequences
+    // ("abcd", "1234") add little to the search space
+    let mut pattern_bits = 0.0;
+    let mut effective_length = 0.0;
+    let mut index = 0;
+    while index < remainder.len() {
+        if is_year(&remainder[index..]) {
+            pattern_bits += 200f64.log2();
+            index += 4;
+            continue;
+        }
+
+        let predictable = index > 0 && {
+            let delta = remainder[index] as i64 - remainder[index - 1] as i64;
+            delta == 0 || delta.abs() == 1
+        };
+
+        effective_length += if predictable { 0.25 } else { 1.0 };
+        index += 1;
+    }
+
+    dictionary_bits + pattern_bits + effective_length * charset_bits
+}
+
+fn is_year(chars: &[char]) -> bool {
+    match chars {
+        ['1', '9', c, d, ..] | ['2', '0', c, d, ..] => c.is_ascii_digit() && d.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+fn charset_size(chars: &[char]) -> f64 {
+    let mut size = 1.0;
+
+    if chars.iter().any(|c| c.is_ascii_lowercase()) {
+        size += 26.0;
+    }
+    if chars.iter().any(|c| c.is_ascii_uppercase()) {
+        size += 26.0;
+    }
+    if chars.iter().any(|c| c.is_ascii_digit()) {
+        size += 10.0;
+    }
+    if chars.iter().any(|c| c.is_ascii_punctuation() || *c == ' ') {
+        size += 33.0;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        size += 100.0;
+    }
+
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 10,
+            max_length: 128,
+            min_score: 3,
+        }
+    }
+
+    fn error_codes(result: Result<(), ValidationErrors>) -> Vec<String> {
+        match result {
+            Ok(()) => vec![],
+            Err(errors) => errors
+                .field_errors()
+                .get("password")
+                .map(|errors| errors.iter().map(|error| error.code.to_string()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn accepts_strong_password() {
+        let result = policy().check("violet-Kettle-93-quartz", "jane@example.com");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn rejects_short_password() {
+        let codes = error_codes(policy().check("x7#Lq", "jane@example.com"));
+
+        assert!(codes.contains(&String::from("too_short")));
+    }
+
+    #[test]
+    fn rejects_long_password_without_scoring() {
+        let password = "a".repeat(2 * 1024 * 1024);
+
+        let codes = error_codes(policy().check(&password, "jane@example.com"));
+
+        assert_eq!(codes, vec![String::from("too_long")]);
+    }
+
+    #[test]
+    fn rejects_common_password() {
+        let codes = error_codes(policy().check("Password123", "jane@example.com"));
+
+        assert!(codes.contains(&String::from("common_password")));
+    }
+
+    #[test]
+    fn rejects_password_containing_email() {
+        let codes = error_codes(policy().check("janedoe-Kettle-93", "janedoe@example.com"));
+
+        assert!(codes.contains(&String::from("contains_email")));
+    }
+
+    #[test]
+    fn rejects_weak_password() {
+        let codes = error_codes(policy().check("aaaaaaaaaaaa", "jane@example.com"));
+
+        assert!(codes.contains(&String::from("too_weak")));
+    }
+
+    #[test]
+    fn scores_common_prefix_low() {
+        assert!(estimate_password_score("password2024!") < 3);
+        assert!(estimate_password_score("violet-Kettle-93-quartz") >= 3);
+    }
+
+    #[test]
+    fn scores_long_passwords() {
+        let password = "password".repeat(50_000);
+
+        assert_eq!(estimate_password_score(&password), 4);
+    }
+}
@@ -0,0 +1,590 @@
+use ciborium::value::Value;
+use data_encoding::BASE64URL_NOPAD;
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+// Authenticator data flags, see https://www.w3.org/TR/webauthn-2/#authenticator-data
+pub const FLAG_USER_PRESENT: u8 = 0x01;
+pub const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+// COSE algorithm identifier for ECDSA w/ SHA-256, the only algorithm we accept
+pub const COSE_ALGORITHM_ES256: i64 = -7;
+
+#[derive(Debug, Deserialize)]
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub ceremony_type: String,
+    pub challenge: String,
+    pub origin: String,
+}
+
+pub struct AuthenticatorData {
+    pub rp_id_hash: Vec<u8>,
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+pub struct AttestedCredential {
+    pub credential_id: Vec<u8>,
+    // Uncompressed SEC1 encoded P-256 public key, `None` when the key type isn't supported
+    pub public_key: Option<Vec<u8>>,
+}
+
+impl AuthenticatorData {
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags & flag == flag
+    }
+}
+
+pub fn generate_challenge() -> String {
+    let mut bytes = [0u8; 32];
+
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+pub fn decode_base64url(input: &str) -> Option<Vec<u8>> {
+    BASE64URL_NOPAD
+        .decode(input.trim_end_matches('=').as_bytes())
+        .ok()
+}
+
+pub fn rp_id_hash(rp_id: &str) -> Vec<u8> {
+    Sha256::digest(rp_id.as_bytes()).to_vec()
+}
+
+// Checks the ceremony type, challenge and origin of the client data
+pub fn verify_client_data(
+    client_data_json: &[u8],
+    ceremony_type: &str,
+    challenge: &str,
+    origin: &str,
+) -> Option<ClientData> {
+    let client_data: ClientData = serde_json::from_slice(client_data_json).ok()?;
+
+    let challenge_matches = client_data.challenge.trim_end_matches('=') == challenge;
+
+    if client_data.ceremony_type != ceremony_type
+        || !challenge_matches
+        || client_data.origin != origin
+    {
+        return None;
+    }
+
+    Some(client_data)
+}
+
+// Extracts the authenticator data from a CBOR encoded attestation object, the attestation
+// statement itself is not verified since we request "none" attestation
+pub fn parse_attestation_object(attestation_object: &[u8]) -> Option<AuthenticatorData> {
+    let value: Value = ciborium::de::from_reader(attestation_object).ok()?;
+
+    let auth_data = value
+        .as_map()?
+        .iter()
+        .find_map(|(key, value)| match key.as_text() {
+            Some("authData") => value.as_bytes().cloned(),
+            _ => None,
+        })?;
+
+    parse_authenticator_data(&auth_data)
+}
+
+pub fn parse_authenticator_data(data: &[u8]) -> Option<AuthenticatorData> {
+    if data.len() < 37 {
+        return None;
+    }
+
+    let rp_id_hash = data[0..32].to_vec();
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes(data[33..37].try_into().ok()?);
+
+    let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL_DATA != 0 {
+        // 16 bytes of AAGUID followed by the credential id length
+        let length_start = 37 + 16;
+        let length_bytes: [u8; 2] = data.get(length_start..length_start + 2)?.try_into().ok()?;
+        let credential_id_length = u16::from_be_bytes(length_bytes) as usize;
+
+        let credential_id_start = length_start + 2;
+        let credential_id_end = credential_id_start + credential_id_length;
+        let credential_id = data.get(credential_id_start..credential_id_end)?.to_vec();
+
+        let cose_key: Value = ciborium::de::from_reader(data.get(credential_id_end..)?).ok()?;
+
+        Some(AttestedCredential {
+            credential_id,
+            public_key: cose_key_to_sec1(&cose_key),
+        })
+    } else {
+        None
+    };
+
+    Some(AuthenticatorData {
+        rp_id_hash,
+        flags,
+        sign_count,
+        attested_credential,
+    })
+}
+
+// Converts an EC2 COSE key using ES256 on the P-256 curve into an uncompressed SEC1 point
+fn cose_key_to_sec1(cose_key: &Value) -> Option<Vec<u8>> {
+    let entries = cose_key.as_map()?;
+
+    let get = |label: i64| {
+        entries.iter().find_map(|(key, value)| {
+            let key = i128::from(key.as_integer()?);
+            (key == label as i128).then_some(value)
+        })
+    };
+
+    let get_integer = |label: i64| {
+        get(label)
+            .and_then(|value| value.as_integer())
+            .map(i128::from)
+    };
+
+    // kty: EC2, alg: ES256, crv: P-256
+    if get_integer(1)? != 2
+        || get_integer(3)? != COSE_ALGORITHM_ES256 as i128
+        || get_integer(-1)? != 1
+    {
+        return None;
+    }
+
+    let x = get(-2)?.as_bytes()?;
+    let y = get(-3)?.as_bytes()?;
+
+    if x.len() != 32 || y.len() != 32 {
+        return None;
+    }
+
+    let mut public_key = Vec::with_capacity(65);
+    public_key.push(0x04);
+    public_key.extend_from_slice(x);
+    public_key.extend_from_slice(y);
+
+    // Make sure the point is actually on the curve before storing it
+    VerifyingKey::from_sec1_bytes(&public_key).ok()?;
+
+    Some(public_key)
+}
+
+// Verifies an assertion signature over the authenticator data and the client data hash
+pub fn verify_assertion_signature(
+    public_key: &[u8],
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+) -> bool {
+    let verifying_key = match VerifyingKey::from_sec1_bytes(public_key) {
+        Ok(verifying_key) => verifying_key,
+        Err(_) => return false,
+    };
+
+    let signature = match Signature::from_der(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+
+    let mut signed_data = authenticator_data.to_vec();
+    signed_data.extend_from_slice(&Sha256::digest(client_data_json));
+
+    verifying_key.verify(&signed_data, &signature).is_ok()
+}
+
+// What a registration or assertion has to match, taken from the stored challenge and the config
+pub struct CeremonyExpectation<'a> {
+    pub challenge: &'a str,
+    pub origin: &'a str,
+    pub rp_id: &'a str,
+}
+
+pub struct VerifiedRegistration {
+    // `None` when the key type isn't supported
+    pub public_key: Option<Vec<u8>>,
+    pub sign_count: u32,
+}
+
+// Verifies the client data and the authenticator data of a new credential
+pub fn verify_registration(
+    client_data_json: &[u8],
+    attestation_object: &[u8],
+    credential_id: &[u8],
+    expected: &CeremonyExpectation,
+) -> Option<VerifiedRegistration> {
+    verify_client_data(
+        client_data_json,
+        "webauthn.create",
+        expected.challenge,
+        expected.origin,
+    )?;
+
+    let authenticator_data = parse_attestation_object(attestation_object)?;
+
+    if authenticator_data.rp_id_hash != rp_id_hash(expected.rp_id)
+        || !authenticator_data.has_flag(FLAG_USER_PRESENT)
+    {
+        return None;
+    }
+
+    let attested_credential = authenticator_data.attested_credential?;
+
+    if attested_credential.credential_id != credential_id {
+        return None;
+    }
+
+    Some(VerifiedRegistration {
+        public_key: attested_credential.public_key,
+        sign_count: authenticator_data.sign_count,
+    })
+}
+
+// Verifies an assertion of a stored credential, returns the new sign count. Passkeys used as
+// the only factor have to prove user verification (PIN, biometrics) on their own
+pub fn verify_assertion(
+    client_data_json: &[u8],
+    authenticator_data: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+    stored_sign_count: u32,
+    require_user_verification: bool,
+    expected: &CeremonyExpectation,
+) -> Option<u32> {
+    verify_client_data(
+        client_data_json,
+        "webauthn.get",
+        expected.challenge,
+        expected.origin,
+    )?;
+
+    let parsed_authenticator_data = parse_authenticator_data(authenticator_data)?;
+
+    if parsed_authenticator_data.rp_id_hash != rp_id_hash(expected.rp_id)
+        || !parsed_authenticator_data.has_flag(FLAG_USER_PRESENT)
+        || (require_user_verification && !parsed_authenticator_data.has_flag(FLAG_USER_VERIFIED))
+    {
+        return None;
+    }
+
+    if !verify_assertion_signature(public_key, authenticator_data, client_data_json, signature) {
+        return None;
+    }
+
+    // A counter that doesn't increase may indicate a cloned authenticator, authenticators that
+    // don't implement the counter always report 0
+    let sign_count = parsed_authenticator_data.sign_count;
+
+    if (sign_count != 0 || stored_sign_count != 0) && sign_count <= stored_sign_count {
+        return None;
+    }
+
+    Some(sign_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use p256::ecdsa::{signature::Signer, SigningKey};
+
+    use super::*;
+
+    const RP_ID: &str = "orvane.test";
+    const ORIGIN: &str = "https://orvane.test";
+    const CHALLENGE: &str = "q2Fz3WvJ0hK8yPn4sTb6cXe1mRa7uLd9gQo5iZw3jVk";
+    const CREDENTIAL_ID: &[u8] = &[0x1f, 0x2e, 0x3d, 0x4c, 0x5b, 0x6a, 0x79, 0x88];
+
+    fn expectation() -> CeremonyExpectation<'static> {
+        CeremonyExpectation {
+            challenge: CHALLENGE,
+            origin: ORIGIN,
+            rp_id: RP_ID,
+        }
+    }
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[0x42; 32].into()).unwrap()
+    }
+
+    fn public_key() -> Vec<u8> {
+        signing_key()
+            .verifying_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn client_data(ceremony_type: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        format!(
+            r#"{{"type":"{}","challenge":"{}","origin":"{}","crossOrigin":false}}"#,
+            ceremony_type, challenge, origin
+        )
+        .into_bytes()
+    }
+
+    fn cose_key(algorithm: i64) -> Vec<u8> {
+        let point = signing_key().verifying_key().to_encoded_point(false);
+        let key = Value::Map(vec![
+            (Value::from(1), Value::from(2)),
+            (Value::from(3), Value::from(algorithm)),
+            (Value::from(-1), Value::from(1)),
+            (Value::from(-2), Value::Bytes(point.x().unwrap().to_vec())),
+            (Value::from(-3), Value::Bytes(point.y().unwrap().to_vec())),
+        ]);
+
+        let mut encoded = vec![];
+        ciborium::ser::into_writer(&key, &mut encoded).unwrap();
+        encoded
+    }
+
+    fn authenticator_data(
+        rp_id: &str,
+        flags: u8,
+        sign_count: u32,
+        attested_key: Option<Vec<u8>>,
+    ) -> Vec<u8> {
+        let mut data = rp_id_hash(rp_id);
+        data.push(flags);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+
+        if let Some(attested_key) = attested_key {
+            data[32] |= FLAG_ATTESTED_CREDENTIAL_DATA;
+            data.extend_from_slice(&[0; 16]);
+            data.extend_from_slice(&(CREDENTIAL_ID.len() as u16).to_be_bytes());
+            data.extend_from_slice(CREDENTIAL_ID);
+            data.extend_from_slice(&attested_key);
+        }
+
+        data
+    }
+
+    fn attestation_object(authenticator_data: Vec<u8>) -> Vec<u8> {
+        let object = Value::Map(vec![
+            (Value::from("fmt"), Value::from("none")),
+            (Value::from("attStmt"), Value::Map(vec![])),
+            (Value::from("authData"), Value::Bytes(authenticator_data)),
+        ]);
+
+        let mut encoded = vec![];
+        ciborium::ser::into_writer(&object, &mut encoded).unwrap();
+        encoded
+    }
+
+    fn sign(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+        let mut signed_data = authenticator_data.to_vec();
+        signed_data.extend_from_slice(&Sha256::digest(client_data_json));
+
+        let signature: Signature = signing_key().sign(&signed_data);
+        signature.to_der().as_bytes().to_vec()
+    }
+
+    fn register(
+        client_data_json: &[u8],
+        authenticator_data: Vec<u8>,
+    ) -> Option<VerifiedRegistration> {
+        verify_registration(
+            client_data_json,
+            &attestation_object(authenticator_data),
+            CREDENTIAL_ID,
+            &expectation(),
+        )
+    }
+
+    fn assert_with(
+        client_data_json: &[u8],
+        authenticator_data: &[u8],
+        stored_sign_count: u32,
+        require_user_verification: bool,
+    ) -> Option<u32> {
+        verify_assertion(
+            client_data_json,
+            authenticator_data,
+            &sign(authenticator_data, client_data_json),
+            &public_key(),
+            stored_sign_count,
+            require_user_verification,
+            &expectation(),
+        )
+    }
+
+    #[test]
+    fn registration_accepts_valid_attestation() {
+        let registration = register(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator_data(RP_ID, FLAG_USER_PRESENT, 0, Some(cose_key(-7))),
+        )
+        .unwrap();
+
+        assert_eq!(registration.public_key, Some(public_key()));
+        assert_eq!(registration.sign_count, 0);
+    }
+
+    #[test]
+    fn registration_rejects_wrong_rp_id_hash() {
+        let registration = register(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator_data("evil.test", FLAG_USER_PRESENT, 0, Some(cose_key(-7))),
+        );
+
+        assert!(registration.is_none());
+    }
+
+    #[test]
+    fn registration_rejects_wrong_challenge() {
+        let registration = register(
+            &client_data("webauthn.create", "c3RhbGUtY2hhbGxlbmdl", ORIGIN),
+            authenticator_data(RP_ID, FLAG_USER_PRESENT, 0, Some(cose_key(-7))),
+        );
+
+        assert!(registration.is_none());
+    }
+
+    #[test]
+    fn registration_rejects_wrong_origin() {
+        let registration = register(
+            &client_data("webauthn.create", CHALLENGE, "https://evil.test"),
+            authenticator_data(RP_ID, FLAG_USER_PRESENT, 0, Some(cose_key(-7))),
+        );
+
+        assert!(registration.is_none());
+    }
+
+    #[test]
+    fn registration_rejects_missing_user_presence() {
+        let registration = register(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator_data(RP_ID, 0, 0, Some(cose_key(-7))),
+        );
+
+        assert!(registration.is_none());
+    }
+
+    #[test]
+    fn registration_rejects_assertion_client_data() {
+        let registration = register(
+            &client_data("webauthn.get", CHALLENGE, ORIGIN),
+            authenticator_data(RP_ID, FLAG_USER_PRESENT, 0, Some(cose_key(-7))),
+        );
+
+        assert!(registration.is_none());
+    }
+
+    #[test]
+    fn registration_flags_unsupported_algorithm() {
+        // RS256
+        let registration = register(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator_data(RP_ID, FLAG_USER_PRESENT, 0, Some(cose_key(-257))),
+        )
+        .unwrap();
+
+        assert!(registration.public_key.is_none());
+    }
+
+    #[test]
+    fn assertion_accepts_valid_signature() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let authenticator_data =
+            authenticator_data(RP_ID, FLAG_USER_PRESENT | FLAG_USER_VERIFIED, 8, None);
+
+        assert_eq!(
+            assert_with(&client_data_json, &authenticator_data, 7, true),
+            Some(8)
+        );
+    }
+
+    #[test]
+    fn assertion_rejects_wrong_rp_id_hash() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let authenticator_data = authenticator_data("evil.test", FLAG_USER_PRESENT, 8, None);
+
+        assert!(assert_with(&client_data_json, &authenticator_data, 7, false).is_none());
+    }
+
+    #[test]
+    fn assertion_rejects_wrong_challenge() {
+        let client_data_json = client_data("webauthn.get", "c3RhbGUtY2hhbGxlbmdl", ORIGIN);
+        let authenticator_data = authenticator_data(RP_ID, FLAG_USER_PRESENT, 8, None);
+
+        assert!(assert_with(&client_data_json, &authenticator_data, 7, false).is_none());
+    }
+
+    #[test]
+    fn assertion_rejects_wrong_origin() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, "https://evil.test");
+        let authenticator_data = authenticator_data(RP_ID, FLAG_USER_PRESENT, 8, None);
+
+        assert!(assert_with(&client_data_json, &authenticator_data, 7, false).is_none());
+    }
+
+    #[test]
+    fn assertion_rejects_missing_user_presence() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let authenticator_data = authenticator_data(RP_ID, FLAG_USER_VERIFIED, 8, None);
+
+        assert!(assert_with(&client_data_json, &authenticator_data, 7, false).is_none());
+    }
+
+    #[test]
+    fn assertion_requires_user_verification_for_passwordless() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let authenticator_data = authenticator_data(RP_ID, FLAG_USER_PRESENT, 8, None);
+
+        assert!(assert_with(&client_data_json, &authenticator_data, 7, true).is_none());
+        assert_eq!(
+            assert_with(&client_data_json, &authenticator_data, 7, false),
+            Some(8)
+        );
+    }
+
+    #[test]
+    fn assertion_rejects_sign_count_regression() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+
+        for sign_count in [0, 6, 7] {
+            let authenticator_data = authenticator_data(RP_ID, FLAG_USER_PRESENT, sign_count, None);
+
+            assert!(assert_with(&client_data_json, &authenticator_data, 7, false).is_none());
+        }
+    }
+
+    #[test]
+    fn assertion_accepts_authenticators_without_counter() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let authenticator_data = authenticator_data(RP_ID, FLAG_USER_PRESENT, 0, None);
+
+        assert_eq!(
+            assert_with(&client_data_json, &authenticator_data, 0, false),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn assertion_rejects_bad_signature() {
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let authenticator_data = authenticator_data(RP_ID, FLAG_USER_PRESENT, 8, None);
+
+        // Signed over different client data
+        let other_client_data = client_data("webauthn.get", "b3RoZXItY2hhbGxlbmdl", ORIGIN);
+        let signature = sign(&authenticator_data, &other_client_data);
+
+        let verify = |signature: &[u8]| {
+            verify_assertion(
+                &client_data_json,
+                &authenticator_data,
+                signature,
+                &public_key(),
+                7,
+                false,
+                &expectation(),
+            )
+        };
+
+        assert!(verify(&signature).is_none());
+        assert!(verify(b"not a signature").is_none());
+    }
+}